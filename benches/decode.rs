@@ -0,0 +1,52 @@
+//! Compares the native EWKB decoder with reading through `postgis::ewkb` and converting,
+//! and measures iterating over the points of an `EwkbView`.
+//!
+//! Run with `cargo bench`.
+
+#[macro_use]
+extern crate criterion;
+extern crate diesel_geography;
+extern crate postgis;
+
+use std::io::Cursor;
+use criterion::{black_box, Criterion, Throughput};
+use diesel_geography::ewkb::{EwkbView, FromEwkb, ToEwkb};
+use diesel_geography::types::*;
+use postgis::ewkb::EwkbRead;
+
+/// A polygon with an outer ring of `n` points and a hole, in SRID 4326.
+fn polygon(n: usize) -> Vec<u8> {
+	let ring = |r: f64| {
+		let mut points: Vec<_> = (0..n)
+			.map(|i| {
+				let a = i as f64 / n as f64 * std::f64::consts::PI * 2.0;
+				GeogPoint { x: 13.4 + r * a.cos(), y: 52.5 + r * a.sin(), srid: Some(4326) }
+			})
+			.collect();
+		points.push(points[0]);
+		points
+	};
+	GeogPolygon { rings: vec![ring(1.0), ring(0.5)], srid: Some(4326) }.to_ewkb()
+}
+
+fn decode(c: &mut Criterion) {
+	for &n in &[16, 10_000] {
+		let bytes = polygon(n);
+		let mut group = c.benchmark_group(format!("polygon/{}", n));
+		group.throughput(Throughput::Bytes(bytes.len() as u64));
+		group.bench_function("native", |b| b.iter(|| GeogPolygon::from_ewkb(black_box(&bytes)).unwrap()));
+		group.bench_function("postgis", |b| {
+			b.iter(|| {
+				let polygon = postgis::ewkb::Polygon::read_ewkb(&mut Cursor::new(black_box(&bytes))).unwrap();
+				GeogPolygon::from(polygon)
+			})
+		});
+		group.bench_function("view", |b| {
+			b.iter(|| EwkbView::new(black_box(&bytes)).unwrap().points().fold(0.0, |sum, p| sum + p.x))
+		});
+		group.finish();
+	}
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
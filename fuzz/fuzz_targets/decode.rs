@@ -0,0 +1,47 @@
+//! Decodes arbitrary bytes as every geography type. Malformed input must fail with an error,
+//! never panic, and whatever decodes must survive another encode/decode round. `EwkbView` must
+//! accept exactly what decodes, apart from values with Z or M coordinates.
+
+#![no_main]
+
+use diesel_geography::error::GeographyError;
+use diesel_geography::ewkb::{EwkbView, FromEwkb, ToEwkb};
+use diesel_geography::types::*;
+use libfuzzer_sys::fuzz_target;
+
+fn decode<T: FromEwkb + ToEwkb>(data: &[u8]) {
+	if let Ok(value) = T::from_ewkb(data) {
+		T::from_ewkb(&value.to_ewkb()).expect("re-encoded value does not decode");
+	}
+}
+
+fuzz_target!(|data: &[u8]| {
+	decode::<GeogPoint>(data);
+	decode::<GeogPointZ>(data);
+	decode::<GeogPointM>(data);
+	decode::<GeogPointZM>(data);
+	decode::<GeogLineString>(data);
+	decode::<GeogPolygon>(data);
+	decode::<GeogMultiPoint>(data);
+	decode::<GeogMultiLineString>(data);
+	decode::<GeogMultiPolygon>(data);
+	decode::<GeogGeometryCollection>(data);
+	decode::<GeogAny>(data);
+	match EwkbView::new(data) {
+		Ok(view) => {
+			// The view also accepts Z and M coordinates, which `GeogAny` cannot hold.
+			match GeogAny::from_ewkb(data) {
+				Ok(_) | Err(GeographyError::WrongDimension { .. }) => {}
+				Err(e) => panic!("view accepts what does not decode: {}", e),
+			}
+			let points = view.points().count();
+			assert!(view.rings().map(Iterator::count).sum::<usize>() <= points);
+			view.bbox();
+		}
+		Err(e) => match GeogAny::from_ewkb(data) {
+			// The dimensions are checked before the rest of the value.
+			Err(GeographyError::WrongDimension { .. }) => {}
+			result => assert_eq!(result.unwrap_err(), e),
+		},
+	}
+});
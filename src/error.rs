@@ -0,0 +1,115 @@
+//! Error types.
+
+use std::error::Error;
+use std::fmt;
+use crate::ewkb::GeometryType;
+use crate::wkt::{Dimension, WktError};
+
+/// An error while constructing a point with [`GeogPoint::builder`](crate::types::GeogPoint::builder).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CoordinateError {
+	/// A longitude outside of `-180..=180` degrees, or not finite.
+	InvalidLongitude(f64),
+	/// A latitude outside of `-90..=90` degrees, or not finite.
+	InvalidLatitude(f64),
+	/// An SRID that is not known to be a geographic (longitude/latitude) reference system.
+	NonGeographicSrid(i32),
+}
+
+impl fmt::Display for CoordinateError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			CoordinateError::InvalidLongitude(lon) => write!(f, "longitude {} is out of range", lon),
+			CoordinateError::InvalidLatitude(lat) => write!(f, "latitude {} is out of range", lat),
+			CoordinateError::NonGeographicSrid(srid) => write!(f, "SRID {} is not a known geographic SRID", srid),
+		}
+	}
+}
+
+impl Error for CoordinateError {}
+
+/// A value whose SRID doesn't match the one required by its type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SridMismatch {
+	pub expected: i32,
+	pub found: Option<i32>,
+}
+
+impl fmt::Display for SridMismatch {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self.found {
+			Some(found) => write!(f, "expected SRID {}, found {}", self.expected, found),
+			None => write!(f, "expected SRID {}, found none", self.expected),
+		}
+	}
+}
+
+impl Error for SridMismatch {}
+
+/// An error while decoding a geography value from EWKB, or loading one from the database.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GeographyError {
+	/// The value holds a different kind of geometry than the Rust type can represent.
+	UnexpectedType { expected: GeometryType, found: GeometryType, srid: Option<i32> },
+	/// The points lack a coordinate the Rust type requires, e.g. Z for [`GeogPointZ`](crate::types::GeogPointZ).
+	WrongDimension { expected: Dimension, found: Dimension },
+	/// An SRID outside of the `0..=999999` range PostGIS allows.
+	InvalidSrid(i32),
+	/// The input ends in the middle of a value, at the given byte offset.
+	Truncated { offset: usize },
+	/// The value ends at the given byte offset, but the input goes on.
+	TrailingBytes { offset: usize },
+	/// A byte order marker other than 0 (big endian) or 1 (little endian).
+	InvalidByteOrder(u8),
+	/// A geometry type code this crate doesn't know.
+	UnknownType(u32),
+	/// Collections nested deeper than the decoder allows.
+	TooDeeplyNested,
+	/// Hex EWKB with a non-hex character at the given offset, or of odd length.
+	InvalidHex { offset: usize },
+	/// A value with another SRID than the one required by [`WithSrid`](crate::types::WithSrid).
+	SridMismatch(SridMismatch),
+	/// A box in text form, as used for [`Box2d`](crate::sql_types::Box2d), that can't be parsed.
+	InvalidBox(WktError),
+}
+
+impl fmt::Display for GeographyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			GeographyError::UnexpectedType { expected, found, srid: Some(srid) } => {
+				write!(f, "expected a {}, found a {} with SRID {}", expected, found, srid)
+			}
+			GeographyError::UnexpectedType { expected, found, srid: None } => {
+				write!(f, "expected a {}, found a {}", expected, found)
+			}
+			GeographyError::WrongDimension { expected, found } => {
+				write!(f, "expected {} coordinates, found {}", expected, found)
+			}
+			GeographyError::InvalidSrid(srid) => write!(f, "invalid SRID {}", srid),
+			GeographyError::Truncated { offset } => write!(f, "EWKB ends unexpectedly at byte {}", offset),
+			GeographyError::TrailingBytes { offset } => write!(f, "unexpected bytes after the EWKB value at byte {}", offset),
+			GeographyError::InvalidByteOrder(b) => write!(f, "invalid EWKB byte order {}", b),
+			GeographyError::UnknownType(t) => write!(f, "unknown geometry type code {}", t),
+			GeographyError::TooDeeplyNested => write!(f, "geometries are nested too deeply"),
+			GeographyError::InvalidHex { offset } => write!(f, "invalid hex EWKB at character {}", offset),
+			GeographyError::SridMismatch(ref e) => e.fmt(f),
+			GeographyError::InvalidBox(ref e) => write!(f, "invalid box: {}", e),
+		}
+	}
+}
+
+impl Error for GeographyError {
+	fn source(&self) -> Option<&(dyn Error + 'static)> {
+		match *self {
+			GeographyError::SridMismatch(ref e) => Some(e),
+			GeographyError::InvalidBox(ref e) => Some(e),
+			_ => None,
+		}
+	}
+}
+
+impl From<SridMismatch> for GeographyError {
+	fn from(e: SridMismatch) -> Self {
+		GeographyError::SridMismatch(e)
+	}
+}
@@ -0,0 +1,603 @@
+//! EWKB encoding and decoding, the format PostGIS uses on the wire.
+//!
+//! The `FromSql` and `ToSql` impls go through these traits, which can also be used directly,
+//! e.g. on EWKB stored outside the database or on the hex EWKB PostGIS prints in text output.
+//!
+//! Decoding reads the bytes directly into the crate's types. EWKB only carries the SRID of the
+//! outermost geometry, and decoded parts such as the points of a linestring inherit it.
+//! Values with Z or M coordinates only decode into the types that hold them, e.g. `POINT Z` into
+//! `GeogPointZ`, so that no coordinates are lost when they are written back.
+//! The input must hold exactly one value; bytes after it are an error.
+//! To look at coordinates without decoding, use [`EwkbView`].
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::{self, Write};
+use crate::error::GeographyError;
+use crate::types::*;
+use crate::wkt::Dimension;
+
+/// Decoding from EWKB.
+pub trait FromEwkb: Sized {
+	/// Decodes a value, failing if the bytes are malformed or hold an incompatible geometry.
+	fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError>;
+
+	/// Decodes a value from hex EWKB such as `0101000020E6100000...`, the text form of
+	/// `geography` and `geometry` in query results and `COPY` output. Either case is accepted.
+	fn from_hex_ewkb(hex: &str) -> Result<Self, GeographyError> {
+		let hex = hex.as_bytes();
+		if !hex.len().is_multiple_of(2) {
+			return Err(GeographyError::InvalidHex { offset: hex.len() });
+		}
+		let nibble = |i: usize| match hex[i] {
+			b @ b'0'..=b'9' => Ok(b - b'0'),
+			b @ b'a'..=b'f' => Ok(b - b'a' + 10),
+			b @ b'A'..=b'F' => Ok(b - b'A' + 10),
+			_ => Err(GeographyError::InvalidHex { offset: i }),
+		};
+		let bytes = (0..hex.len())
+			.step_by(2)
+			.map(|i| Ok(nibble(i)? << 4 | nibble(i + 1)?))
+			.collect::<Result<Vec<u8>, GeographyError>>()?;
+		Self::from_ewkb(&bytes)
+	}
+}
+
+/// Encoding to EWKB. Values are always written in little endian byte order.
+pub trait ToEwkb {
+	fn write_ewkb<W: Write + ?Sized>(&self, out: &mut W) -> io::Result<()>;
+
+	fn to_ewkb(&self) -> Vec<u8> {
+		let mut out = Vec::new();
+		self.write_ewkb(&mut out).expect("writing to a Vec cannot fail");
+		out
+	}
+
+	/// Encodes the value as uppercase hex EWKB, like PostGIS prints it.
+	fn to_hex_ewkb(&self) -> String {
+		const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+		let mut hex = String::new();
+		for b in self.to_ewkb() {
+			hex.push(DIGITS[(b >> 4) as usize] as char);
+			hex.push(DIGITS[(b & 0xf) as usize] as char);
+		}
+		hex
+	}
+}
+
+/// The kind of geometry an EWKB value holds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GeometryType {
+	Point,
+	LineString,
+	Polygon,
+	MultiPoint,
+	MultiLineString,
+	MultiPolygon,
+	GeometryCollection,
+}
+
+impl GeometryType {
+	fn from_code(code: u32) -> Option<Self> {
+		Some(match code {
+			1 => GeometryType::Point,
+			2 => GeometryType::LineString,
+			3 => GeometryType::Polygon,
+			4 => GeometryType::MultiPoint,
+			5 => GeometryType::MultiLineString,
+			6 => GeometryType::MultiPolygon,
+			7 => GeometryType::GeometryCollection,
+			_ => return None,
+		})
+	}
+}
+
+impl fmt::Display for GeometryType {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(self, f)
+	}
+}
+
+const Z_FLAG: u32 = 0x8000_0000;
+const M_FLAG: u32 = 0x4000_0000;
+const SRID_FLAG: u32 = 0x2000_0000;
+/// The bits of a type id left once the flags are masked off, which must hold a code from 1 to 7.
+const TYPE_MASK: u32 = 0x0FFF_FFFF;
+
+/// The largest SRID PostGIS accepts.
+const MAX_SRID: i32 = 999_999;
+
+/// The size of a header without an SRID, the least any geometry takes.
+const MIN_SIZE: usize = 5;
+
+/// How deeply multi geometries and collections may be nested.
+pub(crate) const MAX_DEPTH: usize = 32;
+
+/// The header every EWKB geometry starts with.
+#[derive(Debug, Copy, Clone)]
+struct Header {
+	be: bool,
+	kind: GeometryType,
+	z: bool,
+	m: bool,
+	srid: Option<i32>,
+}
+
+impl Header {
+	/// The size of a point in bytes.
+	fn point_size(&self) -> usize {
+		8 * (2 + self.z as usize + self.m as usize)
+	}
+
+	fn dimension(&self) -> Dimension {
+		match (self.z, self.m) {
+			(false, false) => Dimension::Xy,
+			(true, false) => Dimension::Xyz,
+			(false, true) => Dimension::Xym,
+			(true, true) => Dimension::Xyzm,
+		}
+	}
+
+	/// Fails unless the points have exactly the given dimensions, so that no coordinates are lost.
+	fn check_dimension(&self, expected: Dimension) -> Result<(), GeographyError> {
+		match self.dimension() {
+			found if found == expected => Ok(()),
+			found => Err(GeographyError::WrongDimension { expected, found }),
+		}
+	}
+
+	/// Decodes a point from exactly [`point_size`](Header::point_size) bytes.
+	fn coords(&self, b: &[u8]) -> Coords {
+		let f = |i: usize| {
+			let mut v = [0; 8];
+			v.copy_from_slice(&b[8 * i..8 * i + 8]);
+			if self.be { f64::from_be_bytes(v) } else { f64::from_le_bytes(v) }
+		};
+		let z = if self.z { f(2) } else { 0.0 };
+		let m = if self.m { f(2 + self.z as usize) } else { 0.0 };
+		[f(0), f(1), z, m]
+	}
+}
+
+/// The coordinates of a point, with zero for a missing Z or M.
+type Coords = [f64; 4];
+
+struct Reader<'a> {
+	bytes: &'a [u8],
+	pos: usize,
+}
+
+impl<'a> Reader<'a> {
+	fn new(bytes: &'a [u8]) -> Self {
+		Reader { bytes, pos: 0 }
+	}
+
+	fn take(&mut self, n: usize) -> Result<&'a [u8], GeographyError> {
+		match self.pos.checked_add(n).filter(|&end| end <= self.bytes.len()) {
+			Some(end) => {
+				let taken = &self.bytes[self.pos..end];
+				self.pos = end;
+				Ok(taken)
+			}
+			None => Err(GeographyError::Truncated { offset: self.bytes.len() }),
+		}
+	}
+
+	/// Fails unless the whole input has been read.
+	fn end(&self) -> Result<(), GeographyError> {
+		if self.pos == self.bytes.len() {
+			Ok(())
+		} else {
+			Err(GeographyError::TrailingBytes { offset: self.pos })
+		}
+	}
+
+	fn u32(&mut self, be: bool) -> Result<u32, GeographyError> {
+		let b = self.take(4)?;
+		let b = [b[0], b[1], b[2], b[3]];
+		Ok(if be { u32::from_be_bytes(b) } else { u32::from_le_bytes(b) })
+	}
+
+	/// Reads a count of items that take at least `size` bytes each, failing early if the
+	/// remaining input is too short so that the count can be used to reserve memory.
+	fn count(&mut self, be: bool, size: usize) -> Result<usize, GeographyError> {
+		let count = self.u32(be)? as usize;
+		match count.checked_mul(size) {
+			Some(len) if len <= self.bytes.len() - self.pos => Ok(count),
+			_ => Err(GeographyError::Truncated { offset: self.bytes.len() }),
+		}
+	}
+
+	/// Reads a header, failing unless it is of the `expected` kind (any kind if `None`).
+	fn header(&mut self, expected: Option<GeometryType>) -> Result<Header, GeographyError> {
+		let be = match self.take(1)?[0] {
+			0 => true,
+			1 => false,
+			b => return Err(GeographyError::InvalidByteOrder(b)),
+		};
+		let type_id = self.u32(be)?;
+		let srid = if type_id & SRID_FLAG != 0 {
+			let srid = self.u32(be)? as i32;
+			if !(0..=MAX_SRID).contains(&srid) {
+				return Err(GeographyError::InvalidSrid(srid));
+			}
+			Some(srid)
+		} else {
+			None
+		};
+		let code = type_id & TYPE_MASK;
+		let kind = GeometryType::from_code(code).ok_or(GeographyError::UnknownType(code))?;
+		match expected {
+			Some(expected) if expected != kind => Err(GeographyError::UnexpectedType { expected, found: kind, srid }),
+			_ => Ok(Header { be, kind, z: type_id & Z_FLAG != 0, m: type_id & M_FLAG != 0, srid }),
+		}
+	}
+
+	/// The kind of the members of a multi geometry or collection (any kind if `None`).
+	fn member_kind(kind: GeometryType) -> Option<GeometryType> {
+		match kind {
+			GeometryType::MultiPoint => Some(GeometryType::Point),
+			GeometryType::MultiLineString => Some(GeometryType::LineString),
+			GeometryType::MultiPolygon => Some(GeometryType::Polygon),
+			_ => None,
+		}
+	}
+
+	/// Skips over a geometry, checking that it is well-formed.
+	fn skip(&mut self, depth: usize, expected: Option<GeometryType>) -> Result<Header, GeographyError> {
+		if depth > MAX_DEPTH {
+			return Err(GeographyError::TooDeeplyNested);
+		}
+		let h = self.header(expected)?;
+		match h.kind {
+			GeometryType::Point => {
+				self.take(h.point_size())?;
+			}
+			GeometryType::LineString => {
+				let n = self.count(h.be, h.point_size())?;
+				self.take(n * h.point_size())?;
+			}
+			GeometryType::Polygon => {
+				for _ in 0..self.count(h.be, 4)? {
+					let n = self.count(h.be, h.point_size())?;
+					self.take(n * h.point_size())?;
+				}
+			}
+			kind => {
+				for _ in 0..self.count(h.be, MIN_SIZE)? {
+					self.skip(depth + 1, Self::member_kind(kind))?;
+				}
+			}
+		}
+		Ok(h)
+	}
+
+	fn coords(&mut self, h: &Header) -> Result<Coords, GeographyError> {
+		Ok(h.coords(self.take(h.point_size())?))
+	}
+
+	fn point(&mut self, h: &Header, srid: Option<i32>) -> Result<GeogPoint, GeographyError> {
+		let c = self.coords(h)?;
+		Ok(GeogPoint { x: c[0], y: c[1], srid })
+	}
+
+	fn points(&mut self, h: &Header, srid: Option<i32>) -> Result<Vec<GeogPoint>, GeographyError> {
+		let n = self.count(h.be, h.point_size())?;
+		let mut points = Vec::with_capacity(n);
+		for _ in 0..n {
+			points.push(self.point(h, srid)?);
+		}
+		Ok(points)
+	}
+
+	/// Decodes a 2D geometry of the `expected` kind (any kind if `None`). Parts without an SRID of
+	/// their own get `srid`, the SRID of the enclosing geometry.
+	fn geometry(
+		&mut self,
+		depth: usize,
+		expected: Option<GeometryType>,
+		srid: Option<i32>,
+	) -> Result<GeogAny, GeographyError> {
+		if depth > MAX_DEPTH {
+			return Err(GeographyError::TooDeeplyNested);
+		}
+		let h = self.header(expected)?;
+		h.check_dimension(Dimension::Xy)?;
+		let srid = h.srid.or(srid);
+		Ok(match h.kind {
+			GeometryType::Point => GeogAny::Point(self.point(&h, srid)?),
+			GeometryType::LineString => GeogAny::LineString(GeogLineString { points: self.points(&h, srid)?, srid }),
+			GeometryType::Polygon => {
+				let n = self.count(h.be, 4)?;
+				let mut rings = Vec::with_capacity(n);
+				for _ in 0..n {
+					rings.push(self.points(&h, srid)?);
+				}
+				GeogAny::Polygon(GeogPolygon { rings, srid })
+			}
+			kind => {
+				let n = self.count(h.be, MIN_SIZE)?;
+				let mut members = Vec::with_capacity(n);
+				for _ in 0..n {
+					members.push(self.geometry(depth + 1, Self::member_kind(kind), srid)?);
+				}
+				let members = members.into_iter();
+				match kind {
+					GeometryType::MultiPoint => GeogAny::MultiPoint(GeogMultiPoint {
+						points: members.flat_map(|g| GeogPoint::try_from(g).ok()).collect(),
+						srid,
+					}),
+					GeometryType::MultiLineString => GeogAny::MultiLineString(GeogMultiLineString {
+						lines: members.flat_map(|g| GeogLineString::try_from(g).ok()).collect(),
+						srid,
+					}),
+					GeometryType::MultiPolygon => GeogAny::MultiPolygon(GeogMultiPolygon {
+						polygons: members.flat_map(|g| GeogPolygon::try_from(g).ok()).collect(),
+						srid,
+					}),
+					_ => GeogAny::GeometryCollection(GeogGeometryCollection { geometries: members.collect(), srid }),
+				}
+			}
+		})
+	}
+
+	/// Decodes a point with exactly the given dimensions that makes up the whole input.
+	fn point_with(&mut self, dimension: Dimension) -> Result<(Coords, Option<i32>), GeographyError> {
+		let h = self.header(Some(GeometryType::Point))?;
+		h.check_dimension(dimension)?;
+		let coords = self.coords(&h)?;
+		self.end()?;
+		Ok((coords, h.srid))
+	}
+}
+
+/// Reads the geometry type and SRID from the header, without looking at the rest.
+pub(crate) fn peek(bytes: &[u8]) -> Result<(GeometryType, Option<i32>), GeographyError> {
+	let h = Reader::new(bytes).header(None)?;
+	Ok((h.kind, h.srid))
+}
+
+impl FromEwkb for GeogAny {
+	fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError> {
+		let mut reader = Reader::new(bytes);
+		let g = reader.geometry(0, None, None)?;
+		reader.end()?;
+		Ok(g)
+	}
+}
+
+macro_rules! impl_from_ewkb {
+	($($t:ident => $variant:ident),+) => {$(
+		impl FromEwkb for $t {
+			fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError> {
+				let mut reader = Reader::new(bytes);
+				let g = reader.geometry(0, Some(GeometryType::$variant), None)?;
+				reader.end()?;
+				match g {
+					GeogAny::$variant(g) => Ok(g),
+					_ => unreachable!("decoded a geometry of another kind"),
+				}
+			}
+		}
+	)+};
+}
+
+impl_from_ewkb!(
+	GeogPoint => Point,
+	GeogLineString => LineString,
+	GeogPolygon => Polygon,
+	GeogMultiPoint => MultiPoint,
+	GeogMultiLineString => MultiLineString,
+	GeogMultiPolygon => MultiPolygon,
+	GeogGeometryCollection => GeometryCollection
+);
+
+impl FromEwkb for GeogPointZ {
+	fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError> {
+		let ([x, y, z, _], srid) = Reader::new(bytes).point_with(Dimension::Xyz)?;
+		Ok(GeogPointZ { x, y, z, srid })
+	}
+}
+
+impl FromEwkb for GeogPointM {
+	fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError> {
+		let ([x, y, _, m], srid) = Reader::new(bytes).point_with(Dimension::Xym)?;
+		Ok(GeogPointM { x, y, m, srid })
+	}
+}
+
+impl FromEwkb for GeogPointZM {
+	fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError> {
+		let ([x, y, z, m], srid) = Reader::new(bytes).point_with(Dimension::Xyzm)?;
+		Ok(GeogPointZM { x, y, z, m, srid })
+	}
+}
+
+/// A borrowed view of an EWKB value, for looking at its coordinates without decoding it.
+///
+/// The bytes are checked once when the view is created, so iterating never fails.
+#[derive(Debug, Copy, Clone)]
+pub struct EwkbView<'a> {
+	bytes: &'a [u8],
+	header: Header,
+}
+
+impl<'a> EwkbView<'a> {
+	/// Checks that `bytes` holds exactly one well-formed EWKB value.
+	pub fn new(bytes: &'a [u8]) -> Result<Self, GeographyError> {
+		let mut reader = Reader::new(bytes);
+		let header = reader.skip(0, None)?;
+		reader.end()?;
+		Ok(EwkbView { bytes, header })
+	}
+
+	/// A view of bytes that were already checked by [`new`](EwkbView::new).
+	pub(crate) fn checked(bytes: &'a [u8]) -> Self {
+		let header = Reader::new(bytes).header(None).expect("EWKB was checked before");
+		EwkbView { bytes, header }
+	}
+
+	pub fn geometry_type(&self) -> GeometryType {
+		self.header.kind
+	}
+
+	pub fn srid(&self) -> Option<i32> {
+		self.header.srid
+	}
+
+	/// The dimensions of the outermost geometry's points.
+	pub fn dimension(&self) -> Dimension {
+		self.header.dimension()
+	}
+
+	/// The underlying bytes.
+	pub fn as_bytes(&self) -> &'a [u8] {
+		self.bytes
+	}
+
+	/// All points of the geometry in order, e.g. ring after ring for a polygon.
+	/// Z and M coordinates are skipped; the points get the SRID of the view.
+	pub fn points(&self) -> Points<'a> {
+		let mut points = Points::run(&[], self.header, self.header.srid);
+		points.reader = Reader::new(self.bytes);
+		points.stack[0] = Frame { remaining: 1, rings: None };
+		points.depth = 1;
+		points
+	}
+
+	/// All linestrings and polygon rings of the geometry in order, each as an iterator over its points.
+	/// Points, including the members of multipoints, are not part of any ring.
+	pub fn rings(&self) -> Rings<'a> {
+		Rings { points: self.points() }
+	}
+
+	/// The 2D bounding box of all points, or `None` if the geometry is empty.
+	pub fn bbox(&self) -> Option<GeogBox> {
+		self.points().fold(None, |bbox, p| {
+			Some(match bbox {
+				None => GeogBox { xmin: p.x, ymin: p.y, xmax: p.x, ymax: p.y },
+				Some(b) => GeogBox {
+					xmin: b.xmin.min(p.x),
+					ymin: b.ymin.min(p.y),
+					xmax: b.xmax.max(p.x),
+					ymax: b.ymax.max(p.y),
+				},
+			})
+		})
+	}
+}
+
+/// A level of nesting while iterating over points.
+#[derive(Debug, Copy, Clone)]
+struct Frame {
+	/// Members or rings left at this level.
+	remaining: u32,
+	/// For the rings of a polygon, the polygon's header; `None` for geometries with their own header.
+	rings: Option<Header>,
+}
+
+/// Iterator over the points of an [`EwkbView`].
+pub struct Points<'a> {
+	reader: Reader<'a>,
+	srid: Option<i32>,
+	/// The bytes of the points left in the current linestring, ring or point.
+	run: &'a [u8],
+	run_header: Header,
+	stack: [Frame; MAX_DEPTH + 2],
+	depth: usize,
+}
+
+impl<'a> Points<'a> {
+	/// An iterator over a single run of points.
+	fn run(run: &'a [u8], run_header: Header, srid: Option<i32>) -> Self {
+		Points {
+			reader: Reader::new(&[]),
+			srid,
+			run,
+			run_header,
+			stack: [Frame { remaining: 0, rings: None }; MAX_DEPTH + 2],
+			depth: 0,
+		}
+	}
+
+	/// Moves to the next run of points. The bytes were checked by [`EwkbView::new`], so reads cannot fail.
+	#[inline(never)]
+	fn next_run(&mut self) -> Option<()> {
+		loop {
+			let frame = self.stack[..self.depth].last_mut()?;
+			if frame.remaining == 0 {
+				self.depth -= 1;
+				continue;
+			}
+			frame.remaining -= 1;
+			let (h, count) = match frame.rings {
+				Some(h) => (h, self.reader.u32(h.be).ok()?),
+				None => {
+					let h = self.reader.header(None).ok()?;
+					match h.kind {
+						GeometryType::Point => (h, 1),
+						GeometryType::LineString => (h, self.reader.u32(h.be).ok()?),
+						kind => {
+							let remaining = self.reader.u32(h.be).ok()?;
+							let rings = if kind == GeometryType::Polygon { Some(h) } else { None };
+							self.stack[self.depth] = Frame { remaining, rings };
+							self.depth += 1;
+							continue;
+						}
+					}
+				}
+			};
+			self.run = self.reader.take(count as usize * h.point_size()).ok()?;
+			self.run_header = h;
+			return Some(());
+		}
+	}
+}
+
+impl<'a> Iterator for Points<'a> {
+	type Item = GeogPoint;
+
+	fn next(&mut self) -> Option<GeogPoint> {
+		while self.run.is_empty() {
+			self.next_run()?;
+		}
+		let (point, rest) = self.run.split_at(self.run_header.point_size());
+		self.run = rest;
+		let c = self.run_header.coords(point);
+		Some(GeogPoint { x: c[0], y: c[1], srid: self.srid })
+	}
+
+	// Iterating run by run lets the compiler hoist the byte order and dimension checks out of the loop.
+	fn fold<B, F: FnMut(B, GeogPoint) -> B>(mut self, init: B, mut f: F) -> B {
+		let mut acc = init;
+		loop {
+			let h = self.run_header;
+			for point in self.run.chunks_exact(h.point_size()) {
+				let c = h.coords(point);
+				acc = f(acc, GeogPoint { x: c[0], y: c[1], srid: self.srid });
+			}
+			if self.next_run().is_none() {
+				return acc;
+			}
+		}
+	}
+}
+
+/// Iterator over the rings of an [`EwkbView`]. Each ring is an iterator over its points.
+pub struct Rings<'a> {
+	points: Points<'a>,
+}
+
+impl<'a> Iterator for Rings<'a> {
+	type Item = Points<'a>;
+
+	fn next(&mut self) -> Option<Points<'a>> {
+		loop {
+			self.points.next_run()?;
+			let h = self.points.run_header;
+			if h.kind != GeometryType::Point {
+				return Some(Points::run(self.points.run, h, self.points.srid));
+			}
+		}
+	}
+}
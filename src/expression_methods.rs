@@ -0,0 +1,111 @@
+//! PostGIS spatial operators and casts as Diesel expression methods.
+
+use diesel::expression::{AsExpression, Expression};
+use diesel::pg::Pg;
+use diesel::sql_types::Double;
+use crate::sql_types::{GeographyOrNullable, GeometryOrNullable, TypedGeographyOrNullable};
+
+infix_operator!(BboxOverlaps, " && ", backend: Pg);
+infix_operator!(DistanceKnn, " <-> ", Double, backend: Pg);
+infix_operator!(SameAs, " ~= ", backend: Pg);
+
+pub trait GeographyExpressionMethods: Expression + Sized {
+	/// Whether the bounding boxes of both geographies intersect (`&&`). Uses spatial indexes.
+	fn bbox_overlaps<T>(self, other: T) -> BboxOverlaps<Self, T::Expression>
+	where
+		Self::SqlType: GeographyOrNullable,
+		T: AsExpression<Self::SqlType>,
+	{
+		BboxOverlaps::new(self, other.as_expression())
+	}
+
+	/// Distance between both geographies in meters (`<->`), for index-assisted
+	/// nearest-neighbour ordering in `.order()`.
+	fn distance_knn<T>(self, other: T) -> DistanceKnn<Self, T::Expression>
+	where
+		Self::SqlType: GeographyOrNullable,
+		T: AsExpression<Self::SqlType>,
+	{
+		DistanceKnn::new(self, other.as_expression())
+	}
+
+	/// Whether both geographies have the same bounding box (`~=`). PostGIS only defines this
+	/// operator for `geometry`, so both sides are cast to `geometry`.
+	fn same_as<T>(self, other: T) -> SameAs<AsGeometry<Self>, AsGeometry<T::Expression>>
+	where
+		Self::SqlType: GeographyOrNullable,
+		T: AsExpression<Self::SqlType>,
+	{
+		SameAs::new(self.as_geometry(), other.as_expression().as_geometry())
+	}
+
+	/// The geography cast to `geometry`, e.g. for planar functions such as
+	/// [`st_transform`](fn@crate::functions::st_transform). Coordinates and SRID are kept.
+	#[allow(clippy::wrong_self_convention)]
+	fn as_geometry(self) -> AsGeometry<Self> {
+		AsGeometry { expr: self }
+	}
+}
+
+impl<T> GeographyExpressionMethods for T
+where
+	T: Expression,
+	T::SqlType: GeographyOrNullable,
+{
+}
+
+pub trait GeometryExpressionMethods: Expression + Sized {
+	/// The geometry cast to `geography`. PostGIS assumes SRID 4326 if the geometry has none.
+	#[allow(clippy::wrong_self_convention)]
+	fn as_geography(self) -> AsGeography<Self> {
+		AsGeography { expr: self }
+	}
+}
+
+impl<T> GeometryExpressionMethods for T
+where
+	T: Expression,
+	T::SqlType: GeometryOrNullable,
+{
+}
+
+pub trait TypedGeographyExpressionMethods: Expression + Sized {
+	/// The [`TypedGeography`](crate::sql_types::TypedGeography) expression as plain `geography`,
+	/// for the functions and operators. The SRID stays the same.
+	#[allow(clippy::wrong_self_convention)]
+	fn as_geography(self) -> TypedAsGeography<Self> {
+		TypedAsGeography { expr: self }
+	}
+}
+
+impl<T> TypedGeographyExpressionMethods for T
+where
+	T: Expression,
+	T::SqlType: TypedGeographyOrNullable,
+{
+}
+
+cast_expression!(
+	/// A `geography` expression cast to `geometry`, see [`GeographyExpressionMethods::as_geometry`].
+	AsGeometry,
+	<E::SqlType as GeographyOrNullable>::Geometry,
+	"geometry",
+	where E::SqlType: GeographyOrNullable
+);
+
+cast_expression!(
+	/// A `geometry` expression cast to `geography`, see [`GeometryExpressionMethods::as_geography`].
+	AsGeography,
+	<E::SqlType as GeometryOrNullable>::Geography,
+	"geography",
+	where E::SqlType: GeometryOrNullable
+);
+
+cast_expression!(
+	/// A [`TypedGeography`](crate::sql_types::TypedGeography) expression as plain `geography`,
+	/// see [`TypedGeographyExpressionMethods::as_geography`].
+	TypedAsGeography,
+	<E::SqlType as TypedGeographyOrNullable>::Geography,
+	"geography",
+	where E::SqlType: TypedGeographyOrNullable
+);
@@ -0,0 +1,222 @@
+//! PostGIS functions on geography values.
+//!
+//! Distances, lengths and areas are in meters (square meters for areas), angles in radians.
+//! By default PostGIS computes them on the spheroid; the `*_with_spheroid` variants let
+//! you pass `false` to use the faster spherical calculation instead.
+//!
+//! The bounding box functions `st_extent`, `st_3dextent` and `st_makeenvelope` work on `geometry`,
+//! as do `box2d_as_geometry` and `box3d_as_geometry`, which turn bound boxes into polygons.
+
+use diesel::expression::AsExpression;
+use diesel::sql_types::*;
+use crate::sql_types::*;
+
+define_sql_function! {
+	/// Minimum distance between two geographies.
+	#[sql_name = "ST_Distance"]
+	fn st_distance(a: Geography, b: Geography) -> Double;
+}
+
+define_sql_function! {
+	/// Minimum distance between two geographies, choosing between spheroid and sphere.
+	#[sql_name = "ST_Distance"]
+	fn st_distance_with_spheroid(a: Geography, b: Geography, use_spheroid: Bool) -> Double;
+}
+
+define_sql_function! {
+	/// Whether two geographies are within `distance` meters of each other. Uses spatial indexes.
+	#[sql_name = "ST_DWithin"]
+	fn st_dwithin(a: Geography, b: Geography, distance: Double) -> Bool;
+}
+
+define_sql_function! {
+	/// Whether two geographies are within `distance` meters of each other, choosing between spheroid and sphere.
+	#[sql_name = "ST_DWithin"]
+	fn st_dwithin_with_spheroid(a: Geography, b: Geography, distance: Double, use_spheroid: Bool) -> Bool;
+}
+
+define_sql_function! {
+	/// Area of a polygonal geography.
+	#[sql_name = "ST_Area"]
+	fn st_area(g: Geography) -> Double;
+}
+
+define_sql_function! {
+	/// Area of a polygonal geography, choosing between spheroid and sphere.
+	#[sql_name = "ST_Area"]
+	fn st_area_with_spheroid(g: Geography, use_spheroid: Bool) -> Double;
+}
+
+define_sql_function! {
+	/// Length of a linear geography.
+	#[sql_name = "ST_Length"]
+	fn st_length(g: Geography) -> Double;
+}
+
+define_sql_function! {
+	/// Length of a linear geography, choosing between spheroid and sphere.
+	#[sql_name = "ST_Length"]
+	fn st_length_with_spheroid(g: Geography, use_spheroid: Bool) -> Double;
+}
+
+define_sql_function! {
+	/// Length of the boundary of a polygonal geography.
+	#[sql_name = "ST_Perimeter"]
+	fn st_perimeter(g: Geography) -> Double;
+}
+
+define_sql_function! {
+	/// Length of the boundary of a polygonal geography, choosing between spheroid and sphere.
+	#[sql_name = "ST_Perimeter"]
+	fn st_perimeter_with_spheroid(g: Geography, use_spheroid: Bool) -> Double;
+}
+
+define_sql_function! {
+	/// Azimuth from point `a` to point `b`, clockwise from north. `NULL` if the points coincide.
+	#[sql_name = "ST_Azimuth"]
+	fn st_azimuth(a: Geography, b: Geography) -> Nullable<Double>;
+}
+
+define_sql_function! {
+	/// The point reached by moving `distance` meters from `g` along `azimuth`.
+	#[sql_name = "ST_Project"]
+	fn st_project(g: Geography, distance: Double, azimuth: Double) -> Geography;
+}
+
+define_sql_function! {
+	/// The area within `radius` meters of `g`.
+	#[sql_name = "ST_Buffer"]
+	fn st_buffer(g: Geography, radius: Double) -> Geography;
+}
+
+define_sql_function! {
+	/// Whether two geographies share any portion of space.
+	#[sql_name = "ST_Intersects"]
+	fn st_intersects(a: Geography, b: Geography) -> Bool;
+}
+
+define_sql_function! {
+	/// The portion of space shared by two geographies.
+	#[sql_name = "ST_Intersection"]
+	fn st_intersection(a: Geography, b: Geography) -> Geography;
+}
+
+define_sql_function! {
+	/// Whether no point of `b` lies outside of `a`.
+	#[sql_name = "ST_Covers"]
+	fn st_covers(a: Geography, b: Geography) -> Bool;
+}
+
+define_sql_function! {
+	/// Whether no point of `a` lies outside of `b`.
+	#[sql_name = "ST_CoveredBy"]
+	fn st_coveredby(a: Geography, b: Geography) -> Bool;
+}
+
+define_sql_function! {
+	/// The geodesic center of mass of a geography.
+	#[sql_name = "ST_Centroid"]
+	fn st_centroid(g: Geography) -> Geography;
+}
+
+define_sql_function! {
+	/// Adds vertices so that no segment is longer than `max_segment_length` meters.
+	#[sql_name = "ST_Segmentize"]
+	fn st_segmentize(g: Geography, max_segment_length: Double) -> Geography;
+}
+
+define_sql_function! {
+	/// The SRID of a geography.
+	#[sql_name = "ST_SRID"]
+	fn st_srid(g: Geography) -> Integer;
+}
+
+define_sql_function! {
+	/// Parses a geography from WKT or EWKT.
+	#[sql_name = "ST_GeogFromText"]
+	fn st_geogfromtext(text: Text) -> Geography;
+}
+
+define_sql_function! {
+	/// The WKT representation of a geography.
+	#[sql_name = "ST_AsText"]
+	fn st_astext(g: Geography) -> Text;
+}
+
+define_sql_function! {
+	/// The GeoJSON representation of a geography.
+	#[sql_name = "ST_AsGeoJSON"]
+	fn st_asgeojson(g: Geography) -> Text;
+}
+
+define_sql_function! {
+	/// A rectangular polygon from the given minimum and maximum coordinates, e.g. a map viewport.
+	#[sql_name = "ST_MakeEnvelope"]
+	fn st_makeenvelope(xmin: Double, ymin: Double, xmax: Double, ymax: Double, srid: Integer) -> Geometry;
+}
+
+define_sql_function! {
+	/// The geometry reprojected to the spatial reference system `srid`.
+	#[sql_name = "ST_Transform"]
+	fn st_transform(g: Geometry, srid: Integer) -> Geometry;
+}
+
+mod raw {
+	use crate::sql_types::*;
+
+	define_sql_function! {
+		#[aggregate]
+		#[sql_name = "ST_Extent"]
+		fn st_extent(g: Geometry) -> Nullable<Box2d>;
+	}
+
+	define_sql_function! {
+		#[aggregate]
+		#[sql_name = "ST_3DExtent"]
+		fn st_3dextent(g: Geometry) -> Nullable<Box3d>;
+	}
+}
+
+/// The bounding box of all geometries in a group (an aggregate). `NULL` for an empty group.
+pub fn st_extent<G: AsExpression<Geometry>>(g: G) -> BoxAsText<raw::st_extent<G>> {
+	BoxAsText { expr: raw::st_extent(g) }
+}
+
+/// The 3D bounding box of all geometries in a group (an aggregate). `NULL` for an empty group.
+pub fn st_3dextent<G: AsExpression<Geometry>>(g: G) -> BoxAsText<raw::st_3dextent<G>> {
+	BoxAsText { expr: raw::st_3dextent(g) }
+}
+
+/// A `box2d` value such as a bound [`GeogBox`](crate::types::GeogBox) as a rectangular `geometry` polygon
+/// without SRID. Use [`as_geography`](crate::expression_methods::GeometryExpressionMethods::as_geography)
+/// to compare it with geography columns, which assumes SRID 4326.
+pub fn box2d_as_geometry<B: AsExpression<Box2d>>(b: B) -> Box2dAsGeometry<B::Expression> {
+	Box2dAsGeometry { expr: b.as_expression() }
+}
+
+/// A `box3d` value such as a bound [`GeogBox3d`](crate::types::GeogBox3d) as a `geometry` without SRID,
+/// see [`box2d_as_geometry`].
+pub fn box3d_as_geometry<B: AsExpression<Box3d>>(b: B) -> Box3dAsGeometry<B::Expression> {
+	Box3dAsGeometry { expr: b.as_expression() }
+}
+
+cast_expression!(
+	/// A box-valued expression cast to `text`, which is how [`Box2d`] and [`Box3d`] values are transferred.
+	BoxAsText,
+	E::SqlType,
+	"text"
+);
+
+cast_expression!(
+	/// A [`Box2d`] expression cast to `geometry`, see [`box2d_as_geometry`].
+	Box2dAsGeometry,
+	Geometry,
+	"box2d" "geometry"
+);
+
+cast_expression!(
+	/// A [`Box3d`] expression cast to `geometry`, see [`box3d_as_geometry`].
+	Box3dAsGeometry,
+	Geometry,
+	"box3d" "geometry"
+);
@@ -0,0 +1,175 @@
+//! Conversions to and from the `geo-types` crate. The SRID handling is documented on the impls.
+
+use std::convert::TryFrom;
+use geo_types as geo;
+use crate::types::*;
+
+fn point(c: geo::Coord<f64>) -> GeogPoint {
+	GeogPoint { x: c.x, y: c.y, srid: None }
+}
+
+fn points(l: geo::LineString<f64>) -> Vec<GeogPoint> {
+	l.0.into_iter().map(point).collect()
+}
+
+fn line_string(ps: Vec<GeogPoint>) -> geo::LineString<f64> {
+	ps.into_iter().map(|p| geo::Coord { x: p.x, y: p.y }).collect()
+}
+
+/// Drops the SRID, as `geo-types` geometries have none.
+impl From<GeogPoint> for geo::Point<f64> {
+	fn from(p: GeogPoint) -> Self {
+		geo::Point::new(p.x, p.y)
+	}
+}
+/// The point has an SRID of `None`, like all values converted from `geo-types`. PostGIS reads a
+/// missing SRID in a `geography` value as 4326, so set `srid` if the coordinates use another
+/// reference system.
+impl From<geo::Point<f64>> for GeogPoint {
+	fn from(p: geo::Point<f64>) -> Self {
+		point(p.0)
+	}
+}
+
+/// Drops the SRID.
+impl From<GeogLineString> for geo::LineString<f64> {
+	fn from(l: GeogLineString) -> Self {
+		line_string(l.points)
+	}
+}
+/// The linestring and its points have no SRID.
+impl From<geo::LineString<f64>> for GeogLineString {
+	fn from(l: geo::LineString<f64>) -> Self {
+		Self { points: points(l), srid: None }
+	}
+}
+
+/// The first ring becomes the exterior, the others the interiors, and the SRID is dropped.
+///
+/// `geo-types` closes open rings, so e.g. `POLYGON((0 0,1 0,0 1))` gains a final `0 0`.
+impl From<GeogPolygon> for geo::Polygon<f64> {
+	fn from(p: GeogPolygon) -> Self {
+		let mut rings = p.rings.into_iter().map(line_string);
+		let exterior = rings.next().unwrap_or_else(|| geo::LineString(vec![]));
+		geo::Polygon::new(exterior, rings.collect())
+	}
+}
+/// An empty exterior results in a polygon without rings. The polygon has no SRID.
+impl From<geo::Polygon<f64>> for GeogPolygon {
+	fn from(p: geo::Polygon<f64>) -> Self {
+		let (exterior, interiors) = p.into_inner();
+		let rings = if exterior.0.is_empty() && interiors.is_empty() {
+			vec![]
+		} else {
+			Some(exterior).into_iter().chain(interiors).map(points).collect()
+		};
+		Self { rings, srid: None }
+	}
+}
+
+/// Drops the SRID.
+impl From<GeogMultiPoint> for geo::MultiPoint<f64> {
+	fn from(p: GeogMultiPoint) -> Self {
+		p.points.into_iter().map(geo::Point::from).collect()
+	}
+}
+/// The result has no SRID.
+impl From<geo::MultiPoint<f64>> for GeogMultiPoint {
+	fn from(p: geo::MultiPoint<f64>) -> Self {
+		Self { points: p.0.into_iter().map(GeogPoint::from).collect(), srid: None }
+	}
+}
+
+/// Drops the SRID.
+impl From<GeogMultiLineString> for geo::MultiLineString<f64> {
+	fn from(l: GeogMultiLineString) -> Self {
+		l.lines.into_iter().map(geo::LineString::from).collect()
+	}
+}
+/// The result has no SRID.
+impl From<geo::MultiLineString<f64>> for GeogMultiLineString {
+	fn from(l: geo::MultiLineString<f64>) -> Self {
+		Self { lines: l.0.into_iter().map(GeogLineString::from).collect(), srid: None }
+	}
+}
+
+/// Drops the SRID.
+impl From<GeogMultiPolygon> for geo::MultiPolygon<f64> {
+	fn from(p: GeogMultiPolygon) -> Self {
+		p.polygons.into_iter().map(geo::Polygon::from).collect()
+	}
+}
+/// The result has no SRID.
+impl From<geo::MultiPolygon<f64>> for GeogMultiPolygon {
+	fn from(p: geo::MultiPolygon<f64>) -> Self {
+		Self { polygons: p.0.into_iter().map(GeogPolygon::from).collect(), srid: None }
+	}
+}
+
+/// Drops the SRID.
+impl From<GeogGeometryCollection> for geo::GeometryCollection<f64> {
+	fn from(c: GeogGeometryCollection) -> Self {
+		c.geometries.into_iter().map(geo::Geometry::from).collect()
+	}
+}
+/// The result has no SRID.
+impl From<geo::GeometryCollection<f64>> for GeogGeometryCollection {
+	fn from(c: geo::GeometryCollection<f64>) -> Self {
+		Self { geometries: c.0.into_iter().map(GeogAny::from).collect(), srid: None }
+	}
+}
+
+/// Drops the SRID.
+impl From<GeogAny> for geo::Geometry<f64> {
+	fn from(g: GeogAny) -> Self {
+		match g {
+			GeogAny::Point(g) => geo::Geometry::Point(g.into()),
+			GeogAny::LineString(g) => geo::Geometry::LineString(g.into()),
+			GeogAny::Polygon(g) => geo::Geometry::Polygon(g.into()),
+			GeogAny::MultiPoint(g) => geo::Geometry::MultiPoint(g.into()),
+			GeogAny::MultiLineString(g) => geo::Geometry::MultiLineString(g.into()),
+			GeogAny::MultiPolygon(g) => geo::Geometry::MultiPolygon(g.into()),
+			GeogAny::GeometryCollection(g) => geo::Geometry::GeometryCollection(g.into()),
+		}
+	}
+}
+/// `Line`s become linestrings, `Rect`s and `Triangle`s become polygons. The result has no SRID.
+impl From<geo::Geometry<f64>> for GeogAny {
+	fn from(g: geo::Geometry<f64>) -> Self {
+		match g {
+			geo::Geometry::Point(g) => GeogAny::Point(g.into()),
+			geo::Geometry::Line(g) => GeogAny::LineString(geo::LineString::from(g).into()),
+			geo::Geometry::LineString(g) => GeogAny::LineString(g.into()),
+			geo::Geometry::Polygon(g) => GeogAny::Polygon(g.into()),
+			geo::Geometry::MultiPoint(g) => GeogAny::MultiPoint(g.into()),
+			geo::Geometry::MultiLineString(g) => GeogAny::MultiLineString(g.into()),
+			geo::Geometry::MultiPolygon(g) => GeogAny::MultiPolygon(g.into()),
+			geo::Geometry::GeometryCollection(g) => GeogAny::GeometryCollection(g.into()),
+			geo::Geometry::Rect(g) => GeogAny::Polygon(g.to_polygon().into()),
+			geo::Geometry::Triangle(g) => GeogAny::Polygon(g.to_polygon().into()),
+		}
+	}
+}
+
+macro_rules! impl_try_from_geometry {
+	($($t:ty),+) => {$(
+		/// Fails with the converted value if the geometry is of a different kind.
+		impl TryFrom<geo::Geometry<f64>> for $t {
+			type Error = GeogAny;
+
+			fn try_from(g: geo::Geometry<f64>) -> Result<Self, Self::Error> {
+				Self::try_from(GeogAny::from(g))
+			}
+		}
+	)+};
+}
+
+impl_try_from_geometry!(
+	GeogPoint,
+	GeogLineString,
+	GeogPolygon,
+	GeogMultiPoint,
+	GeogMultiLineString,
+	GeogMultiPolygon,
+	GeogGeometryCollection
+);
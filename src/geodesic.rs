@@ -0,0 +1,174 @@
+//! Client-side distance and bearing calculations on `GeogPoint`.
+//!
+//! Coordinates are read as longitude/latitude in degrees on the WGS84 ellipsoid, whatever the SRID.
+//! Distances are in meters and bearings in radians clockwise from north, like `ST_Distance`,
+//! `ST_Azimuth` and `ST_Project` on geography.
+
+use std::f64::consts::PI;
+use crate::types::GeogPoint;
+
+/// WGS84 semi-major axis in meters.
+const A: f64 = 6378137.0;
+/// WGS84 flattening.
+const F: f64 = 1.0 / 298.257223563;
+/// WGS84 semi-minor axis in meters.
+const B: f64 = A * (1.0 - F);
+/// Mean radius of the WGS84 ellipsoid, the sphere PostGIS uses when `use_spheroid` is `false`.
+const MEAN_RADIUS: f64 = (2.0 * A + B) / 3.0;
+
+const MAX_ITERATIONS: usize = 200;
+const EPSILON: f64 = 1e-12;
+
+/// Result of the inverse problem: distance and the azimuth at the start point.
+struct Inverse {
+	distance: f64,
+	azimuth: f64,
+}
+
+/// Vincenty's inverse formula. `None` if it does not converge, which happens for nearly antipodal points.
+fn inverse(from: &GeogPoint, to: &GeogPoint) -> Option<Inverse> {
+	let l = (to.x - from.x).to_radians();
+	let u1 = ((1.0 - F) * from.y.to_radians().tan()).atan();
+	let u2 = ((1.0 - F) * to.y.to_radians().tan()).atan();
+	let (sin_u1, cos_u1) = u1.sin_cos();
+	let (sin_u2, cos_u2) = u2.sin_cos();
+
+	let mut lambda = l;
+	for _ in 0..MAX_ITERATIONS {
+		let (sin_lambda, cos_lambda) = lambda.sin_cos();
+		let sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+			+ (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2))
+		.sqrt();
+		if sin_sigma == 0.0 {
+			return Some(Inverse { distance: 0.0, azimuth: 0.0 });
+		}
+		let cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+		let sigma = sin_sigma.atan2(cos_sigma);
+		let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+		let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+		// On the equator cos²α is 0 and the term drops out.
+		let cos_2sigma_m = if cos_sq_alpha == 0.0 {
+			0.0
+		} else {
+			cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha
+		};
+		let c = F / 16.0 * cos_sq_alpha * (4.0 + F * (4.0 - 3.0 * cos_sq_alpha));
+		let prev = lambda;
+		lambda = l + (1.0 - c) * F * sin_alpha
+			* (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)));
+
+		if (lambda - prev).abs() < EPSILON {
+			let u_sq = cos_sq_alpha * (A * A - B * B) / (B * B);
+			let a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+			let b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+			let delta_sigma = b * sin_sigma
+				* (cos_2sigma_m + b / 4.0
+					* (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)
+						- b / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+							* (-3.0 + 4.0 * cos_2sigma_m * cos_2sigma_m)));
+			let (sin_lambda, cos_lambda) = lambda.sin_cos();
+			let azimuth = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+			return Some(Inverse {
+				distance: B * a * (sigma - delta_sigma),
+				azimuth: normalize_bearing(azimuth),
+			});
+		}
+	}
+	None
+}
+
+fn normalize_bearing(b: f64) -> f64 {
+	let b = b % (2.0 * PI);
+	if b < 0.0 { b + 2.0 * PI } else { b }
+}
+
+fn normalize_longitude(lon: f64) -> f64 {
+	let lon = (lon + 180.0) % 360.0;
+	if lon < 0.0 { lon + 180.0 } else { lon - 180.0 }
+}
+
+impl GeogPoint {
+	/// Great-circle distance in meters on a sphere with the WGS84 mean radius.
+	///
+	/// Matches `ST_Distance(a, b, false)` to within a millimeter; compared to the spheroidal
+	/// `ST_Distance(a, b)` the error is up to about 0.5%.
+	pub fn haversine_distance(&self, other: &GeogPoint) -> f64 {
+		let (lat1, lat2) = (self.y.to_radians(), other.y.to_radians());
+		let d_lat = lat2 - lat1;
+		let d_lon = (other.x - self.x).to_radians();
+		let h = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+		2.0 * MEAN_RADIUS * h.sqrt().min(1.0).asin()
+	}
+
+	/// Distance in meters on the WGS84 ellipsoid, using Vincenty's formula.
+	///
+	/// Matches `ST_Distance(a, b)` to within a millimeter. Returns `None` for nearly antipodal
+	/// points, where the iteration does not converge; fall back to
+	/// [`haversine_distance`](GeogPoint::haversine_distance) there.
+	pub fn vincenty_distance(&self, other: &GeogPoint) -> Option<f64> {
+		inverse(self, other).map(|i| i.distance)
+	}
+
+	/// The bearing from this point towards `other` on the WGS84 ellipsoid, in radians clockwise from north.
+	///
+	/// Matches `ST_Azimuth(a, b)` to within about 1e-9 radians. Like `ST_Azimuth`, returns `None` for
+	/// identical points, and also for nearly antipodal points where the bearing cannot be computed.
+	pub fn initial_bearing(&self, other: &GeogPoint) -> Option<f64> {
+		if self.x == other.x && self.y == other.y {
+			return None;
+		}
+		inverse(self, other).map(|i| i.azimuth)
+	}
+
+	/// The point reached by travelling `distance` meters from this point along `bearing` (radians
+	/// clockwise from north) on the WGS84 ellipsoid, using Vincenty's direct formula.
+	///
+	/// Matches `ST_Project(g, distance, bearing)` to within a millimeter. The result keeps the
+	/// SRID of this point and has its longitude normalized to [-180, 180).
+	pub fn destination(&self, bearing: f64, distance: f64) -> GeogPoint {
+		let (sin_alpha1, cos_alpha1) = bearing.sin_cos();
+		let tan_u1 = (1.0 - F) * self.y.to_radians().tan();
+		let cos_u1 = 1.0 / (1.0 + tan_u1 * tan_u1).sqrt();
+		let sin_u1 = tan_u1 * cos_u1;
+		let sigma1 = tan_u1.atan2(cos_alpha1);
+		let sin_alpha = cos_u1 * sin_alpha1;
+		let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+		let u_sq = cos_sq_alpha * (A * A - B * B) / (B * B);
+		let a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+		let b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+
+		let mut sigma = distance / (B * a);
+		let mut cos_2sigma_m;
+		let mut i = 0;
+		loop {
+			cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+			let (sin_sigma, cos_sigma) = sigma.sin_cos();
+			let delta_sigma = b * sin_sigma
+				* (cos_2sigma_m + b / 4.0
+					* (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)
+						- b / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma * sin_sigma)
+							* (-3.0 + 4.0 * cos_2sigma_m * cos_2sigma_m)));
+			let prev = sigma;
+			sigma = distance / (B * a) + delta_sigma;
+			i += 1;
+			if (sigma - prev).abs() < EPSILON || i >= MAX_ITERATIONS {
+				break;
+			}
+		}
+
+		let (sin_sigma, cos_sigma) = sigma.sin_cos();
+		let tmp = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+		let lat = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1)
+			.atan2((1.0 - F) * (sin_alpha * sin_alpha + tmp * tmp).sqrt());
+		let lambda = (sin_sigma * sin_alpha1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+		let c = F / 16.0 * cos_sq_alpha * (4.0 + F * (4.0 - 3.0 * cos_sq_alpha));
+		let l = lambda - (1.0 - c) * F * sin_alpha
+			* (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m * cos_2sigma_m)));
+
+		GeogPoint {
+			x: normalize_longitude(self.x + l.to_degrees()),
+			y: lat.to_degrees(),
+			srid: self.srid,
+		}
+	}
+}
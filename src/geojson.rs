@@ -0,0 +1,220 @@
+//! GeoJSON ([RFC 7946](https://tools.ietf.org/html/rfc7946)) representation for the `serde` feature.
+//!
+//! By default the geography types serialize their fields as-is. To use GeoJSON geometry objects
+//! such as `{"type":"Point","coordinates":[13.4,52.5]}` instead, annotate the field:
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Store {
+//!     #[serde(with = "diesel_geography::geojson")]
+//!     location: GeogPoint,
+//! }
+//! ```
+//!
+//! GeoJSON coordinates are always WGS84 longitude/latitude, so the SRID is not serialized and
+//! deserialized values get SRID 4326. Serializing a value with an SRID outside
+//! [`GEOGRAPHIC_SRIDS`] fails, as its coordinates aren't longitudes
+//! and latitudes. Altitudes in positions are ignored.
+
+use std::convert::TryFrom;
+use serde::de::Error;
+use serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+use crate::types::*;
+
+type Position = Vec<f64>;
+
+/// A GeoJSON geometry object.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum Object {
+	Point { coordinates: Position },
+	LineString { coordinates: Vec<Position> },
+	Polygon { coordinates: Vec<Vec<Position>> },
+	MultiPoint { coordinates: Vec<Position> },
+	MultiLineString { coordinates: Vec<Vec<Position>> },
+	MultiPolygon { coordinates: Vec<Vec<Vec<Position>>> },
+	GeometryCollection { geometries: Vec<Object> },
+}
+
+fn position(p: &GeogPoint) -> Position {
+	vec![p.x, p.y]
+}
+
+fn positions(ps: &[GeogPoint]) -> Vec<Position> {
+	ps.iter().map(position).collect()
+}
+
+fn rings(rings: &[Vec<GeogPoint>]) -> Vec<Vec<Position>> {
+	rings.iter().map(|r| positions(r)).collect()
+}
+
+impl<'a> From<&'a GeogPoint> for Object {
+	fn from(p: &'a GeogPoint) -> Self {
+		Object::Point { coordinates: position(p) }
+	}
+}
+
+impl<'a> From<&'a GeogLineString> for Object {
+	fn from(l: &'a GeogLineString) -> Self {
+		Object::LineString { coordinates: positions(&l.points) }
+	}
+}
+
+impl<'a> From<&'a GeogPolygon> for Object {
+	fn from(p: &'a GeogPolygon) -> Self {
+		Object::Polygon { coordinates: rings(&p.rings) }
+	}
+}
+
+impl<'a> From<&'a GeogMultiPoint> for Object {
+	fn from(p: &'a GeogMultiPoint) -> Self {
+		Object::MultiPoint { coordinates: positions(&p.points) }
+	}
+}
+
+impl<'a> From<&'a GeogMultiLineString> for Object {
+	fn from(l: &'a GeogMultiLineString) -> Self {
+		Object::MultiLineString { coordinates: l.lines.iter().map(|l| positions(&l.points)).collect() }
+	}
+}
+
+impl<'a> From<&'a GeogMultiPolygon> for Object {
+	fn from(p: &'a GeogMultiPolygon) -> Self {
+		Object::MultiPolygon { coordinates: p.polygons.iter().map(|p| rings(&p.rings)).collect() }
+	}
+}
+
+impl<'a> From<&'a GeogGeometryCollection> for Object {
+	fn from(c: &'a GeogGeometryCollection) -> Self {
+		Object::GeometryCollection { geometries: c.geometries.iter().map(Object::from).collect() }
+	}
+}
+
+impl<'a> From<&'a GeogAny> for Object {
+	fn from(g: &'a GeogAny) -> Self {
+		match *g {
+			GeogAny::Point(ref p) => p.into(),
+			GeogAny::LineString(ref l) => l.into(),
+			GeogAny::Polygon(ref p) => p.into(),
+			GeogAny::MultiPoint(ref p) => p.into(),
+			GeogAny::MultiLineString(ref l) => l.into(),
+			GeogAny::MultiPolygon(ref p) => p.into(),
+			GeogAny::GeometryCollection(ref c) => c.into(),
+		}
+	}
+}
+
+/// The geography types with a GeoJSON representation, which [`serialize`] accepts.
+pub trait ToGeoJson {
+	/// Serializes the value as a GeoJSON geometry object.
+	fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>;
+}
+
+macro_rules! impl_to_geojson {
+	($($t:ident),+) => {$(
+		impl ToGeoJson for $t {
+			fn serialize_geojson<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				match self.srid() {
+					Some(srid) if !GEOGRAPHIC_SRIDS.contains(&srid) => {
+						Err(ser::Error::custom(format!("SRID {} is not geographic and can't be written as GeoJSON", srid)))
+					}
+					_ => Object::from(self).serialize(serializer),
+				}
+			}
+		}
+	)+};
+}
+
+impl_to_geojson!(
+	GeogPoint,
+	GeogLineString,
+	GeogPolygon,
+	GeogMultiPoint,
+	GeogMultiLineString,
+	GeogMultiPolygon,
+	GeogGeometryCollection,
+	GeogAny
+);
+
+const SRID: Option<i32> = Some(4326);
+
+fn point(p: Position) -> Result<GeogPoint, String> {
+	if p.len() < 2 {
+		return Err(format!("a position needs at least 2 elements, found {}", p.len()));
+	}
+	Ok(GeogPoint { x: p[0], y: p[1], srid: SRID })
+}
+
+fn points(ps: Vec<Position>) -> Result<Vec<GeogPoint>, String> {
+	ps.into_iter().map(point).collect()
+}
+
+fn line_string(ps: Vec<Position>) -> Result<GeogLineString, String> {
+	Ok(GeogLineString { points: points(ps)?, srid: SRID })
+}
+
+fn polygon(rings: Vec<Vec<Position>>) -> Result<GeogPolygon, String> {
+	Ok(GeogPolygon { rings: rings.into_iter().map(points).collect::<Result<_, _>>()?, srid: SRID })
+}
+
+impl TryFrom<Object> for GeogAny {
+	type Error = String;
+
+	fn try_from(g: Object) -> Result<Self, Self::Error> {
+		Ok(match g {
+			Object::Point { coordinates } => GeogAny::Point(point(coordinates)?),
+			Object::LineString { coordinates } => GeogAny::LineString(line_string(coordinates)?),
+			Object::Polygon { coordinates } => GeogAny::Polygon(polygon(coordinates)?),
+			Object::MultiPoint { coordinates } => {
+				GeogAny::MultiPoint(GeogMultiPoint { points: points(coordinates)?, srid: SRID })
+			}
+			Object::MultiLineString { coordinates } => GeogAny::MultiLineString(GeogMultiLineString {
+				lines: coordinates.into_iter().map(line_string).collect::<Result<_, _>>()?,
+				srid: SRID,
+			}),
+			Object::MultiPolygon { coordinates } => GeogAny::MultiPolygon(GeogMultiPolygon {
+				polygons: coordinates.into_iter().map(polygon).collect::<Result<_, _>>()?,
+				srid: SRID,
+			}),
+			Object::GeometryCollection { geometries } => GeogAny::GeometryCollection(GeogGeometryCollection {
+				geometries: geometries.into_iter().map(GeogAny::try_from).collect::<Result<_, _>>()?,
+				srid: SRID,
+			}),
+		})
+	}
+}
+
+fn type_name(g: &GeogAny) -> &'static str {
+	match *g {
+		GeogAny::Point(_) => "Point",
+		GeogAny::LineString(_) => "LineString",
+		GeogAny::Polygon(_) => "Polygon",
+		GeogAny::MultiPoint(_) => "MultiPoint",
+		GeogAny::MultiLineString(_) => "MultiLineString",
+		GeogAny::MultiPolygon(_) => "MultiPolygon",
+		GeogAny::GeometryCollection(_) => "GeometryCollection",
+	}
+}
+
+/// Serializes a geography value as a GeoJSON geometry object.
+///
+/// Fails if the value has an SRID that is not one of the
+/// [`GEOGRAPHIC_SRIDS`], e.g. Web Mercator (3857).
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	T: ToGeoJson,
+	S: Serializer,
+{
+	value.serialize_geojson(serializer)
+}
+
+/// Deserializes a geography value from a GeoJSON geometry object.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+	T: TryFrom<GeogAny>,
+	D: Deserializer<'de>,
+{
+	let any = GeogAny::try_from(Object::deserialize(deserializer)?).map_err(D::Error::custom)?;
+	let found = type_name(&any);
+	T::try_from(any).map_err(|_| D::Error::custom(format!("unexpected GeoJSON type {}", found)))
+}
@@ -1,11 +1,23 @@
 //! Diesel support for PostGIS geography types and functions.
 
-#![allow(proc_macro_derive_resolution_fallback)]
-
 #[macro_use] extern crate diesel;
 extern crate postgis;
 #[cfg(feature = "serde")]
 #[macro_use] extern crate serde;
+#[cfg(feature = "geo-types")]
+extern crate geo_types;
 
+#[macro_use]
+mod macros;
+pub mod error;
 pub mod sql_types;
+pub mod functions;
+pub mod expression_methods;
+pub mod wkt;
+#[cfg(feature = "serde")]
+pub mod geojson;
+#[cfg(feature = "geo-types")]
+mod geo;
 pub mod types;
+pub mod ewkb;
+mod geodesic;
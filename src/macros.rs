@@ -0,0 +1,53 @@
+/// Defines an expression that casts `expr` to the given SQL type, e.g. `CAST(expr AS geometry)`.
+/// With several type names, it is cast to each of them in turn.
+macro_rules! cast_expression {
+	($(#[$attr:meta])* $name:ident, $sql_type:ty, $($sql:literal)+ $(, where $($bound:tt)+)?) => {
+		$(#[$attr])*
+		#[derive(Debug, Clone, Copy)]
+		pub struct $name<E> {
+			expr: E,
+		}
+
+		impl<E: ::diesel::expression::Expression> ::diesel::expression::Expression for $name<E>
+		$(where $($bound)+)?
+		{
+			type SqlType = $sql_type;
+		}
+
+		impl<E: ::diesel::query_builder::QueryFragment<::diesel::pg::Pg>> ::diesel::query_builder::QueryFragment<::diesel::pg::Pg>
+			for $name<E>
+		{
+			fn walk_ast<'b>(
+				&'b self,
+				mut out: ::diesel::query_builder::AstPass<'_, 'b, ::diesel::pg::Pg>,
+			) -> ::diesel::result::QueryResult<()> {
+				for _ in &[$($sql),+] {
+					out.push_sql("CAST(");
+				}
+				self.expr.walk_ast(out.reborrow())?;
+				$(out.push_sql(concat!(" AS ", $sql, ")"));)+
+				Ok(())
+			}
+		}
+
+		impl<E: ::diesel::query_builder::QueryId> ::diesel::query_builder::QueryId for $name<E> {
+			type QueryId = $name<E::QueryId>;
+			const HAS_STATIC_QUERY_ID: bool = E::HAS_STATIC_QUERY_ID;
+		}
+
+		impl<E: ::diesel::expression::ValidGrouping<GB>, GB> ::diesel::expression::ValidGrouping<GB> for $name<E> {
+			type IsAggregate = E::IsAggregate;
+		}
+
+		impl<E: ::diesel::expression::AppearsOnTable<QS>, QS> ::diesel::expression::AppearsOnTable<QS> for $name<E>
+		$(where $($bound)+)?
+		{
+		}
+
+		impl<E: ::diesel::expression::SelectableExpression<QS>, QS> ::diesel::expression::SelectableExpression<QS>
+			for $name<E>
+		$(where $($bound)+)?
+		{
+		}
+	};
+}
@@ -1,5 +1,125 @@
 //! SQL Types.
 
+use std::marker::PhantomData;
+use diesel::query_builder::QueryId;
+use diesel::sql_types::{Nullable, SingleValue};
+
+/// The PostGIS `geography` type, for coordinates on the WGS84 spheroid (or another geographic SRID).
+///
+/// Its OID and array OID are looked up by name on first use, as PostGIS assigns them when the
+/// extension is installed. This makes `Nullable<Geography>` and `Array<Geography>` work like any
+/// built-in type, e.g. `Vec<GeogPoint>` for `geography[]` columns or with `eq_any`.
 #[derive(SqlType, QueryId)]
-#[postgres(type_name = "geography")]
+#[diesel(postgres_type(name = "geography"))]
 pub struct Geography;
+
+/// The PostGIS `geometry` type, for planar coordinates in any SRID.
+#[derive(SqlType, QueryId)]
+#[diesel(postgres_type(name = "geometry"))]
+pub struct Geometry;
+
+/// `Geography` or `Nullable<Geography>`, the SQL types
+/// [`GeographyExpressionMethods`](crate::expression_methods::GeographyExpressionMethods) work on.
+pub trait GeographyOrNullable: SingleValue {
+	/// The `geometry` type with the same nullability.
+	type Geometry: SingleValue;
+}
+
+impl GeographyOrNullable for Geography {
+	type Geometry = Geometry;
+}
+
+impl GeographyOrNullable for Nullable<Geography> {
+	type Geometry = Nullable<Geometry>;
+}
+
+/// `Geometry` or `Nullable<Geometry>`, the SQL types
+/// [`GeometryExpressionMethods`](crate::expression_methods::GeometryExpressionMethods) work on.
+pub trait GeometryOrNullable: SingleValue {
+	/// The `geography` type with the same nullability.
+	type Geography: SingleValue;
+}
+
+impl GeometryOrNullable for Geometry {
+	type Geography = Geography;
+}
+
+impl GeometryOrNullable for Nullable<Geometry> {
+	type Geography = Nullable<Geography>;
+}
+
+/// The PostGIS `box2d` type.
+///
+/// PostGIS only defines a text representation for box types, while Diesel exchanges values in
+/// binary. Values of this type are therefore transferred as `text`: functions returning boxes such as
+/// [`st_extent`](crate::functions::st_extent) cast their result to `text`, and bound values go through
+/// [`box2d_as_geometry`](crate::functions::box2d_as_geometry) where PostGIS expects a geometry.
+/// `box2d` columns cannot be selected directly.
+#[derive(SqlType, QueryId)]
+#[diesel(postgres_type(oid = 25, array_oid = 1009))]
+pub struct Box2d;
+
+/// The PostGIS `box3d` type. Transferred as `text`, like [`Box2d`].
+#[derive(SqlType, QueryId)]
+#[diesel(postgres_type(oid = 25, array_oid = 1009))]
+pub struct Box3d;
+
+/// A `geography` column whose SRID is part of the type, e.g. `TypedGeography<Srid4326>`
+/// for `geography(point, 4326)`. Maps to [`WithSrid`](crate::types::WithSrid) values.
+///
+/// The functions and operators take plain [`Geography`]; convert typed columns with
+/// [`as_geography`](crate::expression_methods::TypedGeographyExpressionMethods::as_geography).
+#[derive(SqlType)]
+#[diesel(postgres_type(name = "geography"))]
+pub struct TypedGeography<S: Srid>(PhantomData<S>);
+
+impl<S: Srid> QueryId for TypedGeography<S> {
+	type QueryId = Self;
+	const HAS_STATIC_QUERY_ID: bool = true;
+}
+
+/// `TypedGeography<S>` or `Nullable<TypedGeography<S>>`, the SQL types
+/// [`TypedGeographyExpressionMethods`](crate::expression_methods::TypedGeographyExpressionMethods) work on.
+pub trait TypedGeographyOrNullable: SingleValue {
+	/// The untyped `geography` type with the same nullability.
+	type Geography: SingleValue;
+}
+
+impl<S: Srid> TypedGeographyOrNullable for TypedGeography<S> {
+	type Geography = Geography;
+}
+
+impl<S: Srid> TypedGeographyOrNullable for Nullable<TypedGeography<S>> {
+	type Geography = Nullable<Geography>;
+}
+
+/// A spatial reference system known at compile time, the parameter of [`TypedGeography`].
+///
+/// Implement it on your own marker type to use a reference system that isn't listed here.
+pub trait Srid: 'static {
+	const SRID: i32;
+}
+
+/// WGS 84, the default for `geography`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Srid4326;
+
+impl Srid for Srid4326 {
+	const SRID: i32 = 4326;
+}
+
+/// NAD83.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Srid4269;
+
+impl Srid for Srid4269 {
+	const SRID: i32 = 4269;
+}
+
+/// ETRS89.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Srid4258;
+
+impl Srid for Srid4258 {
+	const SRID: i32 = 4258;
+}
@@ -1,22 +1,132 @@
 //! Rust Types.
 
-use std::io::prelude::*;
-use std::convert::From;
+use std::convert::{From, TryFrom};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Deref;
 use diesel::deserialize::{self, FromSql};
 use diesel::serialize::{self, IsNull, Output, ToSql};
-use diesel::pg::Pg;
-use postgis::ewkb::Point;
+use diesel::pg::{Pg, PgValue};
+use postgis::ewkb::{AsEwkbPoint, AsEwkbLineString, AsEwkbPolygon, AsEwkbMultiPoint, AsEwkbMultiLineString};
+use postgis::ewkb::{AsEwkbMultiPolygon, AsEwkbGeometry, AsEwkbGeometryCollection};
+use postgis::ewkb::{Point, PointZ, PointM, PointZM, LineString, Polygon, MultiPoint, MultiLineString, MultiPolygon};
+use postgis::ewkb::{GeometryT, GeometryCollection};
+use crate::ewkb::{self, EwkbView, FromEwkb, GeometryType, Points, Rings, ToEwkb};
 use crate::sql_types::*;
+use crate::error::{CoordinateError, GeographyError, SridMismatch};
+use crate::wkt::Dimension;
+
+/// Implements [`ToEwkb`] by converting through the given `postgis::ewkb` type, and `FromSql`
+/// and `ToSql` on top of [`FromEwkb`] and [`ToEwkb`] for each of the listed SQL types.
+macro_rules! impl_ewkb_sql {
+	($rust:ty, $ewkb:ty, [$($sql:ty),+]) => {
+		impl ToEwkb for $rust {
+			fn write_ewkb<W: std::io::Write + ?Sized>(&self, out: &mut W) -> std::io::Result<()> {
+				use postgis::ewkb::EwkbWrite;
+				<$ewkb>::from(self.clone())
+					.as_ewkb()
+					.write_ewkb(out)
+					.map_err(|e| std::io::Error::other(e.to_string()))
+			}
+		}
+	$(
+		impl FromSql<$sql, Pg> for $rust {
+			fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+				Ok(Self::from_ewkb(bytes.as_bytes())?)
+			}
+		}
+
+		impl ToSql<$sql, Pg> for $rust {
+			fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+				self.write_ewkb(out)?;
+				Ok(IsNull::No)
+			}
+		}
+	)+};
+}
 
 #[derive(Debug, Copy, Clone, PartialEq, FromSqlRow, AsExpression)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[sql_type = "Geography"]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
 pub struct GeogPoint {
 	pub x: f64, // lon
 	pub y: f64, // lat
 	pub srid: Option<i32>,
 }
 
+/// SRIDs of common geographic reference systems, which PostGIS accepts in `geography` values.
+/// Use [`GeogPointBuilder::srid_unchecked`] for others.
+pub const GEOGRAPHIC_SRIDS: &[i32] = &[
+	4326, // WGS 84
+	4269, // NAD83
+	4267, // NAD27
+	4617, // NAD83(CSRS)
+	4258, // ETRS89
+	4230, // ED50
+	4283, // GDA94
+	7844, // GDA2020
+	4167, // NZGD2000
+	4612, // JGD2000
+	6668, // JGD2011
+	4490, // CGCS2000
+	4674, // SIRGAS 2000
+	4148, // Hartebeesthoek94
+];
+
+impl GeogPoint {
+	/// A WGS84 (SRID 4326) point, checking that the coordinates are in range.
+	pub fn wgs84(lon: f64, lat: f64) -> Result<Self, CoordinateError> {
+		Self::builder(lon, lat).build()
+	}
+
+	/// Starts building a point that is validated before it can be used.
+	/// The SRID defaults to 4326.
+	pub fn builder(lon: f64, lat: f64) -> GeogPointBuilder {
+		GeogPointBuilder { lon, lat, srid: 4326, check_srid: true }
+	}
+}
+
+/// Builds a [`GeogPoint`], validating the coordinate ranges and the SRID.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GeogPointBuilder {
+	lon: f64,
+	lat: f64,
+	srid: i32,
+	check_srid: bool,
+}
+
+impl GeogPointBuilder {
+	/// Sets the SRID, which must be one of the [`GEOGRAPHIC_SRIDS`].
+	pub fn srid(mut self, srid: i32) -> Self {
+		self.srid = srid;
+		self.check_srid = true;
+		self
+	}
+
+	/// Sets an SRID that is not in [`GEOGRAPHIC_SRIDS`], e.g. 4979 (WGS 84 3D) or a custom
+	/// entry in `spatial_ref_sys`. PostGIS still rejects it if it isn't geographic.
+	pub fn srid_unchecked(mut self, srid: i32) -> Self {
+		self.srid = srid;
+		self.check_srid = false;
+		self
+	}
+
+	pub fn build(self) -> Result<GeogPoint, CoordinateError> {
+		let GeogPointBuilder { lon, lat, srid, check_srid } = self;
+		if !(-180.0..=180.0).contains(&lon) {
+			return Err(CoordinateError::InvalidLongitude(lon));
+		}
+		if !(-90.0..=90.0).contains(&lat) {
+			return Err(CoordinateError::InvalidLatitude(lat));
+		}
+		if check_srid && !GEOGRAPHIC_SRIDS.contains(&srid) {
+			return Err(CoordinateError::NonGeographicSrid(srid));
+		}
+		Ok(GeogPoint { x: lon, y: lat, srid: Some(srid) })
+	}
+}
+
 impl From<Point> for GeogPoint {
 	fn from(p: Point) -> Self {
 		let Point { x, y, srid } = p;
@@ -30,20 +140,661 @@ impl From<GeogPoint> for Point {
 	}
 }
 
-impl FromSql<Geography, Pg> for GeogPoint {
-	fn from_sql(bytes: Option<&[u8]>) -> deserialize::Result<Self> {
-		use std::io::Cursor;
-		use postgis::ewkb::EwkbRead;
-		let bytes = not_none!(bytes);
-		let mut rdr = Cursor::new(bytes);
-		Ok(Point::read_ewkb(&mut rdr)?.into())
+impl_ewkb_sql!(GeogPoint, Point, [Geography, Geometry]);
+
+/// A point with an elevation (`PointZ`).
+#[derive(Debug, Copy, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogPointZ {
+	pub x: f64, // lon
+	pub y: f64, // lat
+	pub z: f64,
+	pub srid: Option<i32>,
+}
+
+impl From<PointZ> for GeogPointZ {
+	fn from(p: PointZ) -> Self {
+		let PointZ { x, y, z, srid } = p;
+		Self { x, y, z, srid }
+	}
+}
+impl From<GeogPointZ> for PointZ {
+	fn from(p: GeogPointZ) -> Self {
+		let GeogPointZ { x, y, z, srid } = p;
+		Self { x, y, z, srid }
+	}
+}
+
+impl_ewkb_sql!(GeogPointZ, PointZ, [Geography, Geometry]);
+
+/// A point with a measure (`PointM`).
+#[derive(Debug, Copy, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogPointM {
+	pub x: f64, // lon
+	pub y: f64, // lat
+	pub m: f64,
+	pub srid: Option<i32>,
+}
+
+impl From<PointM> for GeogPointM {
+	fn from(p: PointM) -> Self {
+		let PointM { x, y, m, srid } = p;
+		Self { x, y, m, srid }
+	}
+}
+impl From<GeogPointM> for PointM {
+	fn from(p: GeogPointM) -> Self {
+		let GeogPointM { x, y, m, srid } = p;
+		Self { x, y, m, srid }
+	}
+}
+
+impl_ewkb_sql!(GeogPointM, PointM, [Geography, Geometry]);
+
+/// A point with both an elevation and a measure (`PointZM`).
+#[derive(Debug, Copy, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogPointZM {
+	pub x: f64, // lon
+	pub y: f64, // lat
+	pub z: f64,
+	pub m: f64,
+	pub srid: Option<i32>,
+}
+
+impl From<PointZM> for GeogPointZM {
+	fn from(p: PointZM) -> Self {
+		let PointZM { x, y, z, m, srid } = p;
+		Self { x, y, z, m, srid }
 	}
 }
+impl From<GeogPointZM> for PointZM {
+	fn from(p: GeogPointZM) -> Self {
+		let GeogPointZM { x, y, z, m, srid } = p;
+		Self { x, y, z, m, srid }
+	}
+}
+
+impl_ewkb_sql!(GeogPointZM, PointZM, [Geography, Geometry]);
 
-impl ToSql<Geography, Pg> for GeogPoint {
-	fn to_sql<W: Write>(&self, out: &mut Output<W, Pg>) -> serialize::Result {
-		use postgis::ewkb::{AsEwkbPoint, EwkbWrite};
-		Point::from(*self).as_ewkb().write_ewkb(out)?;
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogLineString {
+	pub points: Vec<GeogPoint>,
+	pub srid: Option<i32>,
+}
+
+impl From<LineString> for GeogLineString {
+	fn from(l: LineString) -> Self {
+		let LineString { points, srid } = l;
+		Self { points: points.into_iter().map(GeogPoint::from).collect(), srid }
+	}
+}
+impl From<GeogLineString> for LineString {
+	fn from(l: GeogLineString) -> Self {
+		let GeogLineString { points, srid } = l;
+		Self { points: points.into_iter().map(Point::from).collect(), srid }
+	}
+}
+
+impl_ewkb_sql!(GeogLineString, LineString, [Geography, Geometry]);
+
+/// A polygon, stored as a list of rings with the exterior ring first.
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogPolygon {
+	pub rings: Vec<Vec<GeogPoint>>,
+	pub srid: Option<i32>,
+}
+
+impl From<Polygon> for GeogPolygon {
+	fn from(p: Polygon) -> Self {
+		let Polygon { rings, srid } = p;
+		let rings = rings.into_iter()
+			.map(|ring| ring.points.into_iter().map(GeogPoint::from).collect())
+			.collect();
+		Self { rings, srid }
+	}
+}
+impl From<GeogPolygon> for Polygon {
+	fn from(p: GeogPolygon) -> Self {
+		let GeogPolygon { rings, srid } = p;
+		let rings = rings.into_iter()
+			.map(|ring| LineString { points: ring.into_iter().map(Point::from).collect(), srid })
+			.collect();
+		Self { rings, srid }
+	}
+}
+
+impl_ewkb_sql!(GeogPolygon, Polygon, [Geography, Geometry]);
+
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogMultiPolygon {
+	pub polygons: Vec<GeogPolygon>,
+	pub srid: Option<i32>,
+}
+
+impl From<MultiPolygon> for GeogMultiPolygon {
+	fn from(p: MultiPolygon) -> Self {
+		let MultiPolygon { polygons, srid } = p;
+		Self { polygons: polygons.into_iter().map(GeogPolygon::from).collect(), srid }
+	}
+}
+impl From<GeogMultiPolygon> for MultiPolygon {
+	fn from(p: GeogMultiPolygon) -> Self {
+		let GeogMultiPolygon { polygons, srid } = p;
+		Self { polygons: polygons.into_iter().map(Polygon::from).collect(), srid }
+	}
+}
+
+impl_ewkb_sql!(GeogMultiPolygon, MultiPolygon, [Geography, Geometry]);
+
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogMultiPoint {
+	pub points: Vec<GeogPoint>,
+	pub srid: Option<i32>,
+}
+
+impl From<MultiPoint> for GeogMultiPoint {
+	fn from(p: MultiPoint) -> Self {
+		let MultiPoint { points, srid } = p;
+		Self { points: points.into_iter().map(GeogPoint::from).collect(), srid }
+	}
+}
+impl From<GeogMultiPoint> for MultiPoint {
+	fn from(p: GeogMultiPoint) -> Self {
+		let GeogMultiPoint { points, srid } = p;
+		Self { points: points.into_iter().map(Point::from).collect(), srid }
+	}
+}
+
+impl_ewkb_sql!(GeogMultiPoint, MultiPoint, [Geography, Geometry]);
+
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogMultiLineString {
+	pub lines: Vec<GeogLineString>,
+	pub srid: Option<i32>,
+}
+
+impl From<MultiLineString> for GeogMultiLineString {
+	fn from(l: MultiLineString) -> Self {
+		let MultiLineString { lines, srid } = l;
+		Self { lines: lines.into_iter().map(GeogLineString::from).collect(), srid }
+	}
+}
+impl From<GeogMultiLineString> for MultiLineString {
+	fn from(l: GeogMultiLineString) -> Self {
+		let GeogMultiLineString { lines, srid } = l;
+		Self { lines: lines.into_iter().map(LineString::from).collect(), srid }
+	}
+}
+
+impl_ewkb_sql!(GeogMultiLineString, MultiLineString, [Geography, Geometry]);
+
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct GeogGeometryCollection {
+	pub geometries: Vec<GeogAny>,
+	pub srid: Option<i32>,
+}
+
+impl From<GeometryCollection> for GeogGeometryCollection {
+	fn from(c: GeometryCollection) -> Self {
+		let GeometryCollection { geometries, srid } = c;
+		Self { geometries: geometries.into_iter().map(GeogAny::from).collect(), srid }
+	}
+}
+impl From<GeogGeometryCollection> for GeometryCollection {
+	fn from(c: GeogGeometryCollection) -> Self {
+		let GeogGeometryCollection { geometries, srid } = c;
+		Self { geometries: geometries.into_iter().map(GeometryT::from).collect(), srid }
+	}
+}
+
+impl_ewkb_sql!(GeogGeometryCollection, GeometryCollection, [Geography, Geometry]);
+
+/// Any geography value, for columns that mix geometry kinds.
+///
+/// Decoding dispatches on the type code in the EWKB header, so unlike the
+/// concrete types this never fails because of an unexpected geometry kind.
+#[derive(Debug, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub enum GeogAny {
+	Point(GeogPoint),
+	LineString(GeogLineString),
+	Polygon(GeogPolygon),
+	MultiPoint(GeogMultiPoint),
+	MultiLineString(GeogMultiLineString),
+	MultiPolygon(GeogMultiPolygon),
+	GeometryCollection(GeogGeometryCollection),
+}
+
+impl GeogAny {
+	pub fn srid(&self) -> Option<i32> {
+		match *self {
+			GeogAny::Point(ref g) => g.srid,
+			GeogAny::LineString(ref g) => g.srid,
+			GeogAny::Polygon(ref g) => g.srid,
+			GeogAny::MultiPoint(ref g) => g.srid,
+			GeogAny::MultiLineString(ref g) => g.srid,
+			GeogAny::MultiPolygon(ref g) => g.srid,
+			GeogAny::GeometryCollection(ref g) => g.srid,
+		}
+	}
+}
+
+macro_rules! impl_geog_any_variant {
+	($($variant:ident($t:ty)),+) => {$(
+		impl From<$t> for GeogAny {
+			fn from(g: $t) -> Self {
+				GeogAny::$variant(g)
+			}
+		}
+
+		/// Fails with the original value if it holds a different kind of geometry.
+		impl TryFrom<GeogAny> for $t {
+			type Error = GeogAny;
+
+			fn try_from(g: GeogAny) -> Result<Self, Self::Error> {
+				match g {
+					GeogAny::$variant(g) => Ok(g),
+					g => Err(g),
+				}
+			}
+		}
+	)+};
+}
+
+impl_geog_any_variant!(
+	Point(GeogPoint),
+	LineString(GeogLineString),
+	Polygon(GeogPolygon),
+	MultiPoint(GeogMultiPoint),
+	MultiLineString(GeogMultiLineString),
+	MultiPolygon(GeogMultiPolygon),
+	GeometryCollection(GeogGeometryCollection)
+);
+
+impl From<GeometryT<Point>> for GeogAny {
+	fn from(g: GeometryT<Point>) -> Self {
+		match g {
+			GeometryT::Point(g) => GeogAny::Point(g.into()),
+			GeometryT::LineString(g) => GeogAny::LineString(g.into()),
+			GeometryT::Polygon(g) => GeogAny::Polygon(g.into()),
+			GeometryT::MultiPoint(g) => GeogAny::MultiPoint(g.into()),
+			GeometryT::MultiLineString(g) => GeogAny::MultiLineString(g.into()),
+			GeometryT::MultiPolygon(g) => GeogAny::MultiPolygon(g.into()),
+			GeometryT::GeometryCollection(g) => GeogAny::GeometryCollection(g.into()),
+		}
+	}
+}
+impl From<GeogAny> for GeometryT<Point> {
+	fn from(g: GeogAny) -> Self {
+		match g {
+			GeogAny::Point(g) => GeometryT::Point(g.into()),
+			GeogAny::LineString(g) => GeometryT::LineString(g.into()),
+			GeogAny::Polygon(g) => GeometryT::Polygon(g.into()),
+			GeogAny::MultiPoint(g) => GeometryT::MultiPoint(g.into()),
+			GeogAny::MultiLineString(g) => GeometryT::MultiLineString(g.into()),
+			GeogAny::MultiPolygon(g) => GeometryT::MultiPolygon(g.into()),
+			GeogAny::GeometryCollection(g) => GeometryT::GeometryCollection(g.into()),
+		}
+	}
+}
+
+impl_ewkb_sql!(GeogAny, GeometryT<Point>, [Geography, Geometry]);
+
+/// A geography value kept as the EWKB it was loaded as, for looking at large geometries without
+/// decoding them, e.g. to compute the bounding box of a coastline.
+///
+/// The bytes are checked once when loading, so the accessors cannot fail.
+/// [`decode`](GeographyRef::decode) converts to one of the owned types when needed.
+#[derive(Debug, Clone, PartialEq, FromSqlRow)]
+pub struct GeographyRef {
+	bytes: Vec<u8>,
+}
+
+impl GeographyRef {
+	pub fn view(&self) -> EwkbView<'_> {
+		EwkbView::checked(&self.bytes)
+	}
+
+	pub fn geometry_type(&self) -> GeometryType {
+		self.view().geometry_type()
+	}
+
+	pub fn srid(&self) -> Option<i32> {
+		self.view().srid()
+	}
+
+	pub fn dimension(&self) -> Dimension {
+		self.view().dimension()
+	}
+
+	/// See [`EwkbView::points`].
+	pub fn points(&self) -> Points<'_> {
+		self.view().points()
+	}
+
+	/// See [`EwkbView::rings`].
+	pub fn rings(&self) -> Rings<'_> {
+		self.view().rings()
+	}
+
+	/// The 2D bounding box of all points, or `None` if the geometry is empty.
+	pub fn bbox(&self) -> Option<GeogBox> {
+		self.view().bbox()
+	}
+
+	/// Decodes the value, failing if it is of another kind or has other dimensions than `T`.
+	pub fn decode<T: FromEwkb>(&self) -> Result<T, GeographyError> {
+		T::from_ewkb(&self.bytes)
+	}
+
+	/// The EWKB bytes.
+	pub fn as_bytes(&self) -> &[u8] {
+		&self.bytes
+	}
+}
+
+impl FromEwkb for GeographyRef {
+	fn from_ewkb(bytes: &[u8]) -> Result<Self, GeographyError> {
+		EwkbView::new(bytes)?;
+		Ok(GeographyRef { bytes: bytes.to_vec() })
+	}
+}
+
+impl FromSql<Geography, Pg> for GeographyRef {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		Ok(Self::from_ewkb(bytes.as_bytes())?)
+	}
+}
+
+impl FromSql<Geometry, Pg> for GeographyRef {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		Ok(Self::from_ewkb(bytes.as_bytes())?)
+	}
+}
+
+/// Raw EWKB, passed through verbatim, e.g. to copy values between databases or hand them to a tile server.
+///
+/// Nothing is checked when loading or storing; the accessors only read the header.
+/// Use [`GeographyRef`] or the owned types to look at the coordinates.
+#[derive(Debug, Clone, PartialEq, Eq, FromSqlRow, AsExpression)]
+#[diesel(sql_type = Geography)]
+#[diesel(sql_type = Geometry)]
+pub struct Ewkb(pub Vec<u8>);
+
+impl Ewkb {
+	pub fn geometry_type(&self) -> Result<GeometryType, GeographyError> {
+		ewkb::peek(&self.0).map(|(kind, _)| kind)
+	}
+
+	pub fn srid(&self) -> Result<Option<i32>, GeographyError> {
+		ewkb::peek(&self.0).map(|(_, srid)| srid)
+	}
+
+	/// Checks the whole value, for looking at its coordinates.
+	pub fn view(&self) -> Result<EwkbView<'_>, GeographyError> {
+		EwkbView::new(&self.0)
+	}
+}
+
+impl ToEwkb for Ewkb {
+	fn write_ewkb<W: std::io::Write + ?Sized>(&self, out: &mut W) -> std::io::Result<()> {
+		out.write_all(&self.0)
+	}
+}
+
+impl From<GeographyRef> for Ewkb {
+	fn from(g: GeographyRef) -> Self {
+		Ewkb(g.bytes)
+	}
+}
+
+impl FromSql<Geography, Pg> for Ewkb {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		Ok(Ewkb(bytes.as_bytes().to_vec()))
+	}
+}
+
+impl FromSql<Geometry, Pg> for Ewkb {
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		Ok(Ewkb(bytes.as_bytes().to_vec()))
+	}
+}
+
+impl ToSql<Geography, Pg> for Ewkb {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		self.write_ewkb(out)?;
+		Ok(IsNull::No)
+	}
+}
+
+impl ToSql<Geometry, Pg> for Ewkb {
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		self.write_ewkb(out)?;
 		Ok(IsNull::No)
 	}
 }
+
+/// A 2D bounding box, as returned by `ST_Extent`. Its text form is `BOX(xmin ymin,xmax ymax)`.
+#[derive(Debug, Copy, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Box2d)]
+pub struct GeogBox {
+	pub xmin: f64,
+	pub ymin: f64,
+	pub xmax: f64,
+	pub ymax: f64,
+}
+
+/// A 3D bounding box, as returned by `ST_3DExtent`. Its text form is `BOX3D(xmin ymin zmin,xmax ymax zmax)`.
+#[derive(Debug, Copy, Clone, PartialEq, FromSqlRow, AsExpression)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[diesel(sql_type = Box3d)]
+pub struct GeogBox3d {
+	pub xmin: f64,
+	pub ymin: f64,
+	pub zmin: f64,
+	pub xmax: f64,
+	pub ymax: f64,
+	pub zmax: f64,
+}
+
+macro_rules! impl_text_sql {
+	($rust:ty, $sql:ty) => {
+		impl FromSql<$sql, Pg> for $rust {
+			fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+				// Invalid UTF-8 turns into replacement characters, which the parser rejects.
+				let text = String::from_utf8_lossy(bytes.as_bytes());
+				Ok(text.parse().map_err(GeographyError::InvalidBox)?)
+			}
+		}
+
+		impl ToSql<$sql, Pg> for $rust {
+			fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+				use std::io::Write;
+				write!(out, "{}", self)?;
+				Ok(IsNull::No)
+			}
+		}
+	};
+}
+
+impl_text_sql!(GeogBox, Box2d);
+impl_text_sql!(GeogBox3d, Box3d);
+
+/// Access to the SRID of a geography value.
+pub trait HasSrid {
+	fn srid(&self) -> Option<i32>;
+	fn set_srid(&mut self, srid: Option<i32>);
+}
+
+macro_rules! impl_has_srid {
+	($($t:ty),+) => {$(
+		impl HasSrid for $t {
+			fn srid(&self) -> Option<i32> {
+				self.srid
+			}
+
+			fn set_srid(&mut self, srid: Option<i32>) {
+				self.srid = srid;
+			}
+		}
+	)+};
+}
+
+impl_has_srid!(
+	GeogPoint,
+	GeogPointZ,
+	GeogPointM,
+	GeogPointZM,
+	GeogLineString,
+	GeogPolygon,
+	GeogMultiPoint,
+	GeogMultiLineString,
+	GeogMultiPolygon,
+	GeogGeometryCollection
+);
+
+impl HasSrid for GeogAny {
+	fn srid(&self) -> Option<i32> {
+		GeogAny::srid(self)
+	}
+
+	fn set_srid(&mut self, srid: Option<i32>) {
+		match *self {
+			GeogAny::Point(ref mut g) => g.srid = srid,
+			GeogAny::LineString(ref mut g) => g.srid = srid,
+			GeogAny::Polygon(ref mut g) => g.srid = srid,
+			GeogAny::MultiPoint(ref mut g) => g.srid = srid,
+			GeogAny::MultiLineString(ref mut g) => g.srid = srid,
+			GeogAny::MultiPolygon(ref mut g) => g.srid = srid,
+			GeogAny::GeometryCollection(ref mut g) => g.srid = srid,
+		}
+	}
+}
+
+/// A geography value whose SRID is fixed by the type `S`, for
+/// [`TypedGeography<S>`](crate::sql_types::TypedGeography) columns.
+///
+/// Comparing or inserting a `WithSrid<_, Srid4269>` into a `TypedGeography<Srid4326>`
+/// column doesn't compile. Loading a row with a different SRID fails with [`SridMismatch`].
+///
+/// ```compile_fail
+/// # extern crate diesel;
+/// # extern crate diesel_geography;
+/// # use diesel::prelude::*;
+/// # use diesel_geography::sql_types::*;
+/// # use diesel_geography::types::*;
+/// # diesel::table! {
+/// #     use diesel::sql_types::*;
+/// #     use diesel_geography::sql_types::*;
+/// #     places (id) {
+/// #         id -> Int4,
+/// #         location -> TypedGeography<Srid4326>,
+/// #     }
+/// # }
+/// # fn main() {
+/// let nad83 = WithSrid::<GeogPoint, Srid4269>::new(GeogPoint { x: -77.0, y: 38.9, srid: None }).unwrap();
+/// let query = places::table.filter(places::location.eq(nad83));
+/// # }
+/// ```
+#[derive(FromSqlRow, AsExpression)]
+#[diesel(sql_type = TypedGeography<S>)]
+pub struct WithSrid<T, S: Srid> {
+	value: T,
+	srid: PhantomData<S>,
+}
+
+impl<T: HasSrid, S: Srid> WithSrid<T, S> {
+	/// Wraps `value`, setting its SRID to `S::SRID` if it has none.
+	/// Fails if it already has a different SRID.
+	pub fn new(mut value: T) -> Result<Self, SridMismatch> {
+		match value.srid() {
+			None => value.set_srid(Some(S::SRID)),
+			Some(srid) if srid == S::SRID => {}
+			found => return Err(SridMismatch { expected: S::SRID, found }),
+		}
+		Ok(Self { value, srid: PhantomData })
+	}
+}
+
+impl<T, S: Srid> WithSrid<T, S> {
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T, S: Srid> Deref for WithSrid<T, S> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.value
+	}
+}
+
+impl<T: fmt::Debug, S: Srid> fmt::Debug for WithSrid<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("WithSrid").field("value", &self.value).field("srid", &S::SRID).finish()
+	}
+}
+
+impl<T: Clone, S: Srid> Clone for WithSrid<T, S> {
+	fn clone(&self) -> Self {
+		Self { value: self.value.clone(), srid: PhantomData }
+	}
+}
+
+impl<T: Copy, S: Srid> Copy for WithSrid<T, S> {}
+
+impl<T: PartialEq, S: Srid> PartialEq for WithSrid<T, S> {
+	fn eq(&self, other: &Self) -> bool {
+		self.value == other.value
+	}
+}
+
+impl<T, S> FromSql<TypedGeography<S>, Pg> for WithSrid<T, S>
+where
+	T: FromSql<Geography, Pg> + HasSrid,
+	S: Srid,
+{
+	fn from_sql(bytes: PgValue<'_>) -> deserialize::Result<Self> {
+		Ok(Self::new(T::from_sql(bytes)?).map_err(GeographyError::from)?)
+	}
+}
+
+impl<T, S> ToSql<TypedGeography<S>, Pg> for WithSrid<T, S>
+where
+	T: ToSql<Geography, Pg>,
+	S: Srid,
+{
+	fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, Pg>) -> serialize::Result {
+		self.value.to_sql(out)
+	}
+}
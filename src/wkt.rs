@@ -0,0 +1,706 @@
+//! WKT and EWKT text representations.
+//!
+//! All geography types implement `Display`, producing EWKT such as `SRID=4326;POINT(13.4 52.5)`
+//! (plain WKT if the SRID is `None`), and `FromStr`, accepting both WKT and EWKT.
+//! Points with extra ordinates use the ISO forms `POINT Z (..)`, `POINT M (..)` and `POINT ZM (..)`;
+//! the PostGIS forms `POINTM(..)` and `POINT(x y z)` are accepted as well.
+//!
+//! Like in EWKB, an empty point is a point with NaN coordinates. It is written as `POINT EMPTY`, or `EMPTY`
+//! inside a multipoint, and such members of multipoints and collections parse back to NaN points.
+//! The point types themselves reject `POINT EMPTY` with [`WktError::EmptyPoint`].
+//! Empty rings, lines and polygons inside a list are written as a bare `EMPTY` as well.
+//!
+//! The bounding box types use the PostGIS text forms `BOX(..)` and `BOX3D(..)` instead.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use crate::ewkb::MAX_DEPTH;
+use crate::types::*;
+
+/// The coordinate dimensions of a geometry.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Dimension {
+	Xy,
+	Xyz,
+	Xym,
+	Xyzm,
+}
+
+impl Dimension {
+	fn suffix(self) -> &'static str {
+		match self {
+			Dimension::Xy => "",
+			Dimension::Xyz => " Z",
+			Dimension::Xym => " M",
+			Dimension::Xyzm => " ZM",
+		}
+	}
+}
+
+impl fmt::Display for Dimension {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match *self {
+			Dimension::Xy => "XY",
+			Dimension::Xyz => "XYZ",
+			Dimension::Xym => "XYM",
+			Dimension::Xyzm => "XYZM",
+		})
+	}
+}
+
+/// An error while parsing WKT or EWKT.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WktError {
+	/// The input ended in the middle of a geometry.
+	UnexpectedEnd,
+	/// A token that is not valid at this position.
+	UnexpectedToken(String),
+	/// A geometry tag this crate doesn't support, e.g. `CIRCULARSTRING`.
+	UnknownType(String),
+	/// The text holds a different kind of geometry than the one being parsed.
+	WrongType { expected: &'static str, found: &'static str },
+	/// The coordinates have a different number of dimensions than the one being parsed.
+	WrongDimension { expected: Dimension, found: Dimension },
+	/// `POINT EMPTY`, which the point types can't represent.
+	EmptyPoint,
+	/// A number that can't be parsed.
+	InvalidNumber(String),
+	/// An `SRID=...;` prefix that isn't a valid integer.
+	InvalidSrid(String),
+	/// Collections nested deeper than EWKB allows.
+	TooDeeplyNested,
+}
+
+impl fmt::Display for WktError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			WktError::UnexpectedEnd => write!(f, "unexpected end of WKT"),
+			WktError::UnexpectedToken(ref t) => write!(f, "unexpected token `{}` in WKT", t),
+			WktError::UnknownType(ref t) => write!(f, "unsupported geometry type `{}`", t),
+			WktError::WrongType { expected, found } => write!(f, "expected {}, found {}", expected, found),
+			WktError::WrongDimension { expected, found } => {
+				write!(f, "expected {} coordinates, found {}", expected, found)
+			}
+			WktError::EmptyPoint => write!(f, "empty points are not supported"),
+			WktError::InvalidNumber(ref n) => write!(f, "invalid number `{}`", n),
+			WktError::InvalidSrid(ref s) => write!(f, "invalid SRID `{}`", s),
+			WktError::TooDeeplyNested => write!(f, "geometries are nested too deeply"),
+		}
+	}
+}
+
+impl Error for WktError {}
+
+// --- Parsing
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+	Word(&'a str),
+	Number(&'a str),
+	Open,
+	Close,
+	Comma,
+	Equals,
+	Semicolon,
+}
+
+impl<'a> fmt::Display for Token<'a> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match *self {
+			Token::Word(w) | Token::Number(w) => f.write_str(w),
+			Token::Open => f.write_str("("),
+			Token::Close => f.write_str(")"),
+			Token::Comma => f.write_str(","),
+			Token::Equals => f.write_str("="),
+			Token::Semicolon => f.write_str(";"),
+		}
+	}
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token<'_>>, WktError> {
+	let mut tokens = vec![];
+	let mut chars = s.char_indices().peekable();
+	while let Some(&(start, c)) = chars.peek() {
+		let single = match c {
+			'(' => Some(Token::Open),
+			')' => Some(Token::Close),
+			',' => Some(Token::Comma),
+			'=' => Some(Token::Equals),
+			';' => Some(Token::Semicolon),
+			_ => None,
+		};
+		if let Some(token) = single {
+			chars.next();
+			tokens.push(token);
+		} else if c.is_whitespace() {
+			chars.next();
+		} else if c.is_ascii_alphabetic() || c.is_ascii_digit() || "+-.".contains(c) {
+			let is_word = c.is_ascii_alphabetic();
+			let mut end = start;
+			while let Some(&(i, c)) = chars.peek() {
+				let continues = if is_word {
+					c.is_ascii_alphanumeric()
+				} else {
+					c.is_ascii_digit() || "+-.eE".contains(c)
+				};
+				if !continues {
+					break;
+				}
+				end = i + c.len_utf8();
+				chars.next();
+			}
+			let text = &s[start..end];
+			tokens.push(if is_word { Token::Word(text) } else { Token::Number(text) });
+		} else {
+			return Err(WktError::UnexpectedToken(c.to_string()));
+		}
+	}
+	Ok(tokens)
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Coord {
+	x: f64,
+	y: f64,
+	z: f64,
+	m: f64,
+}
+
+impl Coord {
+	const EMPTY: Coord = Coord { x: f64::NAN, y: f64::NAN, z: f64::NAN, m: f64::NAN };
+}
+
+#[derive(Debug)]
+enum Geom {
+	Point(Option<Coord>),
+	LineString(Vec<Coord>),
+	Polygon(Vec<Vec<Coord>>),
+	MultiPoint(Vec<Coord>),
+	MultiLineString(Vec<Vec<Coord>>),
+	MultiPolygon(Vec<Vec<Vec<Coord>>>),
+	GeometryCollection(Vec<Geom>),
+}
+
+impl Geom {
+	fn kind(&self) -> &'static str {
+		match *self {
+			Geom::Point(_) => "POINT",
+			Geom::LineString(_) => "LINESTRING",
+			Geom::Polygon(_) => "POLYGON",
+			Geom::MultiPoint(_) => "MULTIPOINT",
+			Geom::MultiLineString(_) => "MULTILINESTRING",
+			Geom::MultiPolygon(_) => "MULTIPOLYGON",
+			Geom::GeometryCollection(_) => "GEOMETRYCOLLECTION",
+		}
+	}
+}
+
+struct Parser<'a> {
+	tokens: Vec<Token<'a>>,
+	pos: usize,
+	/// The dimension of the geometry being parsed, once known.
+	dim: Option<Dimension>,
+	/// How many collections enclose the geometry being parsed.
+	depth: usize,
+}
+
+impl<'a> Parser<'a> {
+	fn peek(&self) -> Option<&Token<'a>> {
+		self.tokens.get(self.pos)
+	}
+
+	fn next(&mut self) -> Result<Token<'a>, WktError> {
+		let token = self.tokens.get(self.pos).cloned().ok_or(WktError::UnexpectedEnd)?;
+		self.pos += 1;
+		Ok(token)
+	}
+
+	fn expect(&mut self, expected: Token) -> Result<(), WktError> {
+		let token = self.next()?;
+		if token == expected { Ok(()) } else { Err(WktError::UnexpectedToken(token.to_string())) }
+	}
+
+	fn next_is(&mut self, expected: Token) -> bool {
+		let is = self.peek() == Some(&expected);
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	fn srid(&mut self) -> Result<Option<i32>, WktError> {
+		match self.peek() {
+			Some(&Token::Word(w)) if w.eq_ignore_ascii_case("SRID") => {}
+			_ => return Ok(None),
+		}
+		self.pos += 1;
+		self.expect(Token::Equals)?;
+		let srid = match self.next()? {
+			Token::Number(n) => n.parse().map_err(|_| WktError::InvalidSrid(n.to_string()))?,
+			t => return Err(WktError::InvalidSrid(t.to_string())),
+		};
+		self.expect(Token::Semicolon)?;
+		Ok(Some(srid))
+	}
+
+	fn geometry(&mut self) -> Result<Geom, WktError> {
+		if self.depth > MAX_DEPTH {
+			return Err(WktError::TooDeeplyNested);
+		}
+		let tag = match self.next()? {
+			Token::Word(w) => w.to_ascii_uppercase(),
+			t => return Err(WktError::UnexpectedToken(t.to_string())),
+		};
+		let tag_dim = if let Some(&Token::Word(w)) = self.peek() {
+			let dim = match &*w.to_ascii_uppercase() {
+				"Z" => Some(Dimension::Xyz),
+				"M" => Some(Dimension::Xym),
+				"ZM" => Some(Dimension::Xyzm),
+				_ => None,
+			};
+			if dim.is_some() {
+				self.pos += 1;
+			}
+			dim
+		} else {
+			None
+		};
+		let (kind, tag_dim) = match tag_dim {
+			Some(dim) => (&*tag, Some(dim)),
+			None if tag.ends_with("ZM") => (&tag[..tag.len() - 2], Some(Dimension::Xyzm)),
+			None if tag.ends_with('M') && tag != "M" => (&tag[..tag.len() - 1], Some(Dimension::Xym)),
+			None if tag.ends_with('Z') && tag != "Z" => (&tag[..tag.len() - 1], Some(Dimension::Xyz)),
+			None => (&*tag, None),
+		};
+		if let Some(dim) = tag_dim {
+			self.set_dim(dim)?;
+		}
+		let empty = self.next_is_empty();
+		Ok(match kind {
+			"POINT" if empty => Geom::Point(None),
+			"POINT" => {
+				self.expect(Token::Open)?;
+				let c = self.coord()?;
+				self.expect(Token::Close)?;
+				Geom::Point(Some(c))
+			}
+			"LINESTRING" => Geom::LineString(self.list_or_empty(empty, &|p| p.coord())?),
+			"POLYGON" => Geom::Polygon(self.list_or_empty(empty, &|p| p.member_list(&|p| p.coord()))?),
+			"MULTIPOINT" => Geom::MultiPoint(self.list_or_empty(empty, &|p| {
+				if p.next_is_empty() {
+					Ok(Coord::EMPTY)
+				} else if p.next_is(Token::Open) {
+					let c = p.coord()?;
+					p.expect(Token::Close)?;
+					Ok(c)
+				} else {
+					p.coord()
+				}
+			})?),
+			"MULTILINESTRING" => Geom::MultiLineString(self.list_or_empty(empty, &|p| p.member_list(&|p| p.coord()))?),
+			"MULTIPOLYGON" => Geom::MultiPolygon(
+				self.list_or_empty(empty, &|p| p.member_list(&|p| p.member_list(&|p| p.coord())))?,
+			),
+			"GEOMETRYCOLLECTION" => {
+				self.depth += 1;
+				let geometries = self.list_or_empty(empty, &|p| p.geometry())?;
+				self.depth -= 1;
+				Geom::GeometryCollection(geometries)
+			}
+			_ => return Err(WktError::UnknownType(tag.clone())),
+		})
+	}
+
+	/// Parses a parenthesized, comma separated list.
+	fn list<T>(&mut self, item: &dyn Fn(&mut Self) -> Result<T, WktError>) -> Result<Vec<T>, WktError> {
+		self.expect(Token::Open)?;
+		let mut items = vec![item(self)?];
+		while self.next_is(Token::Comma) {
+			items.push(item(self)?);
+		}
+		self.expect(Token::Close)?;
+		Ok(items)
+	}
+
+	/// Parses a list unless the geometry was declared `EMPTY`.
+	fn list_or_empty<T>(
+		&mut self,
+		empty: bool,
+		item: &dyn Fn(&mut Self) -> Result<T, WktError>,
+	) -> Result<Vec<T>, WktError> {
+		if empty { Ok(vec![]) } else { self.list(item) }
+	}
+
+	/// Parses a ring, line or polygon inside a list, which may be a bare `EMPTY`.
+	fn member_list<T>(&mut self, item: &dyn Fn(&mut Self) -> Result<T, WktError>) -> Result<Vec<T>, WktError> {
+		let empty = self.next_is_empty();
+		self.list_or_empty(empty, item)
+	}
+
+	/// Skips an `EMPTY` keyword if it comes next.
+	fn next_is_empty(&mut self) -> bool {
+		let is = matches!(self.peek(), Some(&Token::Word(w)) if w.eq_ignore_ascii_case("EMPTY"));
+		if is {
+			self.pos += 1;
+		}
+		is
+	}
+
+	fn set_dim(&mut self, dim: Dimension) -> Result<(), WktError> {
+		match self.dim {
+			Some(expected) if expected != dim => Err(WktError::WrongDimension { expected, found: dim }),
+			_ => {
+				self.dim = Some(dim);
+				Ok(())
+			}
+		}
+	}
+
+	fn coord(&mut self) -> Result<Coord, WktError> {
+		let mut ords = vec![];
+		while let Some(&Token::Number(n)) = self.peek() {
+			ords.push(n.parse::<f64>().map_err(|_| WktError::InvalidNumber(n.to_string()))?);
+			self.pos += 1;
+		}
+		let dim = match (ords.len(), self.dim) {
+			(3, Some(Dimension::Xym)) => Dimension::Xym,
+			(2, _) => Dimension::Xy,
+			(3, _) => Dimension::Xyz,
+			(4, _) => Dimension::Xyzm,
+			_ => return Err(match self.peek() {
+				Some(t) => WktError::UnexpectedToken(t.to_string()),
+				None => WktError::UnexpectedEnd,
+			}),
+		};
+		self.set_dim(dim)?;
+		let (z, m) = match dim {
+			Dimension::Xy => (0.0, 0.0),
+			Dimension::Xyz => (ords[2], 0.0),
+			Dimension::Xym => (0.0, ords[2]),
+			Dimension::Xyzm => (ords[2], ords[3]),
+		};
+		Ok(Coord { x: ords[0], y: ords[1], z, m })
+	}
+}
+
+/// Parses a complete WKT or EWKT string, checking that its coordinates have the dimension `dim`.
+fn parse(s: &str, dim: Dimension) -> Result<(Geom, Option<i32>), WktError> {
+	let mut p = Parser { tokens: tokenize(s)?, pos: 0, dim: None, depth: 0 };
+	let srid = p.srid()?;
+	let geom = p.geometry()?;
+	if let Some(t) = p.peek() {
+		return Err(WktError::UnexpectedToken(t.to_string()));
+	}
+	match p.dim {
+		Some(found) if found != dim => Err(WktError::WrongDimension { expected: dim, found }),
+		_ => Ok((geom, srid)),
+	}
+}
+
+fn wrong_type(expected: &'static str, geom: &Geom) -> WktError {
+	WktError::WrongType { expected, found: geom.kind() }
+}
+
+fn point(c: Coord, srid: Option<i32>) -> GeogPoint {
+	GeogPoint { x: c.x, y: c.y, srid }
+}
+
+fn points(cs: Vec<Coord>, srid: Option<i32>) -> Vec<GeogPoint> {
+	cs.into_iter().map(|c| point(c, srid)).collect()
+}
+
+fn line_string(cs: Vec<Coord>, srid: Option<i32>) -> GeogLineString {
+	GeogLineString { points: points(cs, srid), srid }
+}
+
+fn polygon(rings: Vec<Vec<Coord>>, srid: Option<i32>) -> GeogPolygon {
+	GeogPolygon { rings: rings.into_iter().map(|r| points(r, srid)).collect(), srid }
+}
+
+fn any(geom: Geom, srid: Option<i32>) -> Result<GeogAny, WktError> {
+	Ok(match geom {
+		Geom::Point(None) => return Err(WktError::EmptyPoint),
+		Geom::Point(Some(c)) => GeogAny::Point(point(c, srid)),
+		Geom::LineString(cs) => GeogAny::LineString(line_string(cs, srid)),
+		Geom::Polygon(rings) => GeogAny::Polygon(polygon(rings, srid)),
+		Geom::MultiPoint(cs) => GeogAny::MultiPoint(GeogMultiPoint { points: points(cs, srid), srid }),
+		Geom::MultiLineString(ls) => GeogAny::MultiLineString(GeogMultiLineString {
+			lines: ls.into_iter().map(|l| line_string(l, srid)).collect(),
+			srid,
+		}),
+		Geom::MultiPolygon(ps) => GeogAny::MultiPolygon(GeogMultiPolygon {
+			polygons: ps.into_iter().map(|p| polygon(p, srid)).collect(),
+			srid,
+		}),
+		Geom::GeometryCollection(gs) => GeogAny::GeometryCollection(GeogGeometryCollection {
+			geometries: gs
+				.into_iter()
+				.map(|g| match g {
+					Geom::Point(None) => Ok(GeogAny::Point(point(Coord::EMPTY, srid))),
+					g => any(g, srid),
+				})
+				.collect::<Result<_, _>>()?,
+			srid,
+		}),
+	})
+}
+
+macro_rules! impl_from_str_for_point {
+	($t:ident, $dim:ident, |$c:ident, $srid:ident| $point:expr) => {
+		impl FromStr for $t {
+			type Err = WktError;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				match parse(s, Dimension::$dim)? {
+					(Geom::Point(Some($c)), $srid) => Ok($point),
+					(Geom::Point(None), _) => Err(WktError::EmptyPoint),
+					(geom, _) => Err(wrong_type("POINT", &geom)),
+				}
+			}
+		}
+	};
+}
+
+impl_from_str_for_point!(GeogPoint, Xy, |c, srid| point(c, srid));
+impl_from_str_for_point!(GeogPointZ, Xyz, |c, srid| GeogPointZ { x: c.x, y: c.y, z: c.z, srid });
+impl_from_str_for_point!(GeogPointM, Xym, |c, srid| GeogPointM { x: c.x, y: c.y, m: c.m, srid });
+impl_from_str_for_point!(GeogPointZM, Xyzm, |c, srid| GeogPointZM { x: c.x, y: c.y, z: c.z, m: c.m, srid });
+
+macro_rules! impl_from_str_via_any {
+	($t:ident, $variant:ident, $kind:expr) => {
+		impl FromStr for $t {
+			type Err = WktError;
+
+			fn from_str(s: &str) -> Result<Self, Self::Err> {
+				let (geom, srid) = parse(s, Dimension::Xy)?;
+				match geom {
+					Geom::$variant(..) => match any(geom, srid)? {
+						GeogAny::$variant(g) => Ok(g),
+						_ => unreachable!(),
+					},
+					geom => Err(wrong_type($kind, &geom)),
+				}
+			}
+		}
+	};
+}
+
+impl_from_str_via_any!(GeogLineString, LineString, "LINESTRING");
+impl_from_str_via_any!(GeogPolygon, Polygon, "POLYGON");
+impl_from_str_via_any!(GeogMultiPoint, MultiPoint, "MULTIPOINT");
+impl_from_str_via_any!(GeogMultiLineString, MultiLineString, "MULTILINESTRING");
+impl_from_str_via_any!(GeogMultiPolygon, MultiPolygon, "MULTIPOLYGON");
+impl_from_str_via_any!(GeogGeometryCollection, GeometryCollection, "GEOMETRYCOLLECTION");
+
+impl FromStr for GeogAny {
+	type Err = WktError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (geom, srid) = parse(s, Dimension::Xy)?;
+		any(geom, srid)
+	}
+}
+
+/// Parses the PostGIS text form of a bounding box, returning its min and max corners.
+fn parse_box(s: &str, tag: &'static str, dim: Dimension) -> Result<(Coord, Coord), WktError> {
+	let mut p = Parser { tokens: tokenize(s)?, pos: 0, dim: Some(dim), depth: 0 };
+	match p.next()? {
+		Token::Word(w) if w.eq_ignore_ascii_case(tag) => {}
+		Token::Word(w) => return Err(WktError::UnknownType(w.to_string())),
+		t => return Err(WktError::UnexpectedToken(t.to_string())),
+	}
+	p.expect(Token::Open)?;
+	let min = p.coord()?;
+	p.expect(Token::Comma)?;
+	let max = p.coord()?;
+	p.expect(Token::Close)?;
+	match p.peek() {
+		Some(t) => Err(WktError::UnexpectedToken(t.to_string())),
+		None => Ok((min, max)),
+	}
+}
+
+impl FromStr for GeogBox {
+	type Err = WktError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (min, max) = parse_box(s, "BOX", Dimension::Xy)?;
+		Ok(GeogBox { xmin: min.x, ymin: min.y, xmax: max.x, ymax: max.y })
+	}
+}
+
+impl FromStr for GeogBox3d {
+	type Err = WktError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let (min, max) = parse_box(s, "BOX3D", Dimension::Xyz)?;
+		Ok(GeogBox3d { xmin: min.x, ymin: min.y, zmin: min.z, xmax: max.x, ymax: max.y, zmax: max.z })
+	}
+}
+
+// --- Formatting
+
+fn fmt_srid(f: &mut fmt::Formatter, srid: Option<i32>) -> fmt::Result {
+	match srid {
+		Some(srid) => write!(f, "SRID={};", srid),
+		None => Ok(()),
+	}
+}
+
+/// Writes `items` as a parenthesized, comma separated list, or `EMPTY`.
+fn fmt_list<T>(
+	f: &mut fmt::Formatter,
+	items: &[T],
+	item: fn(&mut fmt::Formatter, &T) -> fmt::Result,
+) -> fmt::Result {
+	if items.is_empty() {
+		return f.write_str("EMPTY");
+	}
+	f.write_str("(")?;
+	for (i, it) in items.iter().enumerate() {
+		if i > 0 {
+			f.write_str(",")?;
+		}
+		item(f, it)?;
+	}
+	f.write_str(")")
+}
+
+/// Writes a geometry tag, followed by a space if the list after it is `EMPTY`.
+fn fmt_tag(f: &mut fmt::Formatter, tag: &str, empty: bool) -> fmt::Result {
+	f.write_str(tag)?;
+	if empty { f.write_str(" ") } else { Ok(()) }
+}
+
+/// Whether `p` is an empty point, which EWKB stores as NaN coordinates.
+fn is_empty(p: &GeogPoint) -> bool {
+	p.x.is_nan() && p.y.is_nan()
+}
+
+fn fmt_coord(f: &mut fmt::Formatter, p: &GeogPoint) -> fmt::Result {
+	write!(f, "{} {}", p.x, p.y)
+}
+
+fn fmt_coords(f: &mut fmt::Formatter, ps: &[GeogPoint]) -> fmt::Result {
+	fmt_list(f, ps, fmt_coord)
+}
+
+fn fmt_rings(f: &mut fmt::Formatter, rings: &[Vec<GeogPoint>]) -> fmt::Result {
+	fmt_list(f, rings, |f, r| fmt_coords(f, r))
+}
+
+/// Writes a geometry without its SRID, as it appears inside a collection.
+fn fmt_any(f: &mut fmt::Formatter, g: &GeogAny) -> fmt::Result {
+	match *g {
+		GeogAny::Point(ref p) if is_empty(p) => f.write_str("POINT EMPTY"),
+		GeogAny::Point(ref p) => {
+			f.write_str("POINT(")?;
+			fmt_coord(f, p)?;
+			f.write_str(")")
+		}
+		GeogAny::LineString(ref l) => {
+			fmt_tag(f, "LINESTRING", l.points.is_empty())?;
+			fmt_coords(f, &l.points)
+		}
+		GeogAny::Polygon(ref p) => {
+			fmt_tag(f, "POLYGON", p.rings.is_empty())?;
+			fmt_rings(f, &p.rings)
+		}
+		GeogAny::MultiPoint(ref p) => {
+			fmt_tag(f, "MULTIPOINT", p.points.is_empty())?;
+			fmt_list(f, &p.points, |f, p| {
+				if is_empty(p) {
+					return f.write_str("EMPTY");
+				}
+				f.write_str("(")?;
+				fmt_coord(f, p)?;
+				f.write_str(")")
+			})
+		}
+		GeogAny::MultiLineString(ref l) => {
+			fmt_tag(f, "MULTILINESTRING", l.lines.is_empty())?;
+			fmt_list(f, &l.lines, |f, l| fmt_coords(f, &l.points))
+		}
+		GeogAny::MultiPolygon(ref p) => {
+			fmt_tag(f, "MULTIPOLYGON", p.polygons.is_empty())?;
+			fmt_list(f, &p.polygons, |f, p| fmt_rings(f, &p.rings))
+		}
+		GeogAny::GeometryCollection(ref c) => {
+			fmt_tag(f, "GEOMETRYCOLLECTION", c.geometries.is_empty())?;
+			fmt_list(f, &c.geometries, fmt_any)
+		}
+	}
+}
+
+impl fmt::Display for GeogAny {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt_srid(f, self.srid())?;
+		fmt_any(f, self)
+	}
+}
+
+macro_rules! impl_display_via_any {
+	($t:ident, $variant:ident) => {
+		impl fmt::Display for $t {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				// Cloning is needed to reuse `fmt_any`; WKT output is not a hot path.
+				fmt::Display::fmt(&GeogAny::$variant(self.clone()), f)
+			}
+		}
+	};
+}
+
+impl_display_via_any!(GeogPoint, Point);
+impl_display_via_any!(GeogLineString, LineString);
+impl_display_via_any!(GeogPolygon, Polygon);
+impl_display_via_any!(GeogMultiPoint, MultiPoint);
+impl_display_via_any!(GeogMultiLineString, MultiLineString);
+impl_display_via_any!(GeogMultiPolygon, MultiPolygon);
+impl_display_via_any!(GeogGeometryCollection, GeometryCollection);
+
+impl fmt::Display for GeogPointZ {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt_srid(f, self.srid)?;
+		if self.x.is_nan() && self.y.is_nan() {
+			return write!(f, "POINT{} EMPTY", Dimension::Xyz.suffix());
+		}
+		write!(f, "POINT{} ({} {} {})", Dimension::Xyz.suffix(), self.x, self.y, self.z)
+	}
+}
+
+impl fmt::Display for GeogPointM {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt_srid(f, self.srid)?;
+		if self.x.is_nan() && self.y.is_nan() {
+			return write!(f, "POINT{} EMPTY", Dimension::Xym.suffix());
+		}
+		write!(f, "POINT{} ({} {} {})", Dimension::Xym.suffix(), self.x, self.y, self.m)
+	}
+}
+
+impl fmt::Display for GeogPointZM {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt_srid(f, self.srid)?;
+		if self.x.is_nan() && self.y.is_nan() {
+			return write!(f, "POINT{} EMPTY", Dimension::Xyzm.suffix());
+		}
+		write!(f, "POINT{} ({} {} {} {})", Dimension::Xyzm.suffix(), self.x, self.y, self.z, self.m)
+	}
+}
+
+impl fmt::Display for GeogBox {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "BOX({} {},{} {})", self.xmin, self.ymin, self.xmax, self.ymax)
+	}
+}
+
+impl fmt::Display for GeogBox3d {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"BOX3D({} {} {},{} {} {})",
+			self.xmin, self.ymin, self.zmin, self.xmax, self.ymax, self.zmax
+		)
+	}
+}
@@ -0,0 +1,319 @@
+//! EWKB decoding and encoding against hand-checked byte strings, without a database.
+
+extern crate diesel_geography;
+
+use diesel_geography::error::GeographyError;
+use diesel_geography::ewkb::{EwkbView, FromEwkb, GeometryType, ToEwkb};
+use diesel_geography::types::*;
+use diesel_geography::wkt::Dimension;
+
+fn hex(s: &str) -> Vec<u8> {
+	let s: String = s.split_whitespace().collect();
+	(0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap()).collect()
+}
+
+// POINT(1 2) in little and big endian, with and without SRID 4326.
+const POINT_LE: &str = "01 01000000 000000000000F03F 0000000000000040";
+const POINT_BE: &str = "00 00000001 3FF0000000000000 4000000000000000";
+const POINT_SRID_LE: &str = "01 01000020 E6100000 000000000000F03F 0000000000000040";
+const POINT_SRID_BE: &str = "00 20000001 000010E6 3FF0000000000000 4000000000000000";
+
+// POINT Z (1 2 3), POINT M (1 2 4) and POINT ZM (1 2 3 4) with SRID 4326.
+const POINT_Z: &str = "01 010000A0 E6100000 000000000000F03F 0000000000000040 0000000000000840";
+const POINT_M: &str = "01 01000060 E6100000 000000000000F03F 0000000000000040 0000000000001040";
+const POINT_ZM: &str =
+	"01 010000E0 E6100000 000000000000F03F 0000000000000040 0000000000000840 0000000000001040";
+
+// SRID=4326;LINESTRING(1 2,3 4)
+const LINE_STRING: &str = "01 02000020 E6100000 02000000
+	000000000000F03F 0000000000000040 0000000000000840 0000000000001040";
+
+// SRID=4326;POLYGON((0 0,1 0,0 1,0 0))
+const POLYGON: &str = "01 03000020 E6100000 01000000 04000000
+	0000000000000000 0000000000000000 000000000000F03F 0000000000000000
+	0000000000000000 000000000000F03F 0000000000000000 0000000000000000";
+
+// SRID=4326;MULTIPOINT(1 2,3 4), in big endian.
+const MULTI_POINT_BE: &str = "00 20000004 000010E6 00000002
+	00 00000001 3FF0000000000000 4000000000000000
+	00 00000001 4008000000000000 4010000000000000";
+
+// SRID=4326;MULTILINESTRING((1 2,3 4),(3 4,1 2))
+const MULTI_LINE_STRING: &str = "01 05000020 E6100000 02000000
+	01 02000000 02000000 000000000000F03F 0000000000000040 0000000000000840 0000000000001040
+	01 02000000 02000000 0000000000000840 0000000000001040 000000000000F03F 0000000000000040";
+
+// SRID=4326;GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(1 2,3 4))
+const COLLECTION: &str = "01 07000020 E6100000 02000000
+	01 01000000 000000000000F03F 0000000000000040
+	01 02000000 02000000 000000000000F03F 0000000000000040 0000000000000840 0000000000001040";
+
+fn point(srid: Option<i32>) -> GeogPoint {
+	GeogPoint { x: 1.0, y: 2.0, srid }
+}
+
+#[test]
+fn decode_points() {
+	assert_eq!(GeogPoint::from_ewkb(&hex(POINT_LE)).unwrap(), point(None));
+	assert_eq!(GeogPoint::from_ewkb(&hex(POINT_BE)).unwrap(), point(None));
+	assert_eq!(GeogPoint::from_ewkb(&hex(POINT_SRID_LE)).unwrap(), point(Some(4326)));
+	assert_eq!(GeogPoint::from_ewkb(&hex(POINT_SRID_BE)).unwrap(), point(Some(4326)));
+}
+
+#[test]
+fn decode_dimensions() {
+	let srid = Some(4326);
+	assert_eq!(GeogPointZ::from_ewkb(&hex(POINT_Z)).unwrap(), GeogPointZ { x: 1.0, y: 2.0, z: 3.0, srid });
+	assert_eq!(GeogPointM::from_ewkb(&hex(POINT_M)).unwrap(), GeogPointM { x: 1.0, y: 2.0, m: 4.0, srid });
+	assert_eq!(
+		GeogPointZM::from_ewkb(&hex(POINT_ZM)).unwrap(),
+		GeogPointZM { x: 1.0, y: 2.0, z: 3.0, m: 4.0, srid }
+	);
+	// Both missing and extra dimensions are an error, so that nothing is lost on the way back.
+	let wrong = |expected, found| GeographyError::WrongDimension { expected, found };
+	assert_eq!(GeogPointZ::from_ewkb(&hex(POINT_SRID_LE)).unwrap_err(), wrong(Dimension::Xyz, Dimension::Xy));
+	assert_eq!(GeogPointM::from_ewkb(&hex(POINT_Z)).unwrap_err(), wrong(Dimension::Xym, Dimension::Xyz));
+	assert_eq!(GeogPointZM::from_ewkb(&hex(POINT_M)).unwrap_err(), wrong(Dimension::Xyzm, Dimension::Xym));
+	assert_eq!(GeogPoint::from_ewkb(&hex(POINT_Z)).unwrap_err(), wrong(Dimension::Xy, Dimension::Xyz));
+	assert_eq!(GeogPointZ::from_ewkb(&hex(POINT_ZM)).unwrap_err(), wrong(Dimension::Xyz, Dimension::Xyzm));
+	assert_eq!(GeogAny::from_ewkb(&hex(POINT_M)).unwrap_err(), wrong(Dimension::Xy, Dimension::Xym));
+	// A collection holding a POINT Z.
+	let bytes = hex("01 07000000 01000000 01 01000080 000000000000F03F 0000000000000040 0000000000000840");
+	assert_eq!(GeogGeometryCollection::from_ewkb(&bytes).unwrap_err(), wrong(Dimension::Xy, Dimension::Xyz));
+}
+
+#[test]
+fn decode_shapes() {
+	let line = GeogLineString::from_ewkb(&hex(LINE_STRING)).unwrap();
+	assert_eq!(line.to_string(), "SRID=4326;LINESTRING(1 2,3 4)");
+	let polygon = GeogPolygon::from_ewkb(&hex(POLYGON)).unwrap();
+	assert_eq!(polygon.to_string(), "SRID=4326;POLYGON((0 0,1 0,0 1,0 0))");
+	let multi_point = GeogAny::from_ewkb(&hex(MULTI_POINT_BE)).unwrap();
+	assert_eq!(multi_point.to_string(), "SRID=4326;MULTIPOINT((1 2),(3 4))");
+	let collection = GeogAny::from_ewkb(&hex(COLLECTION)).unwrap();
+	assert_eq!(collection.to_string(), "SRID=4326;GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(1 2,3 4))");
+}
+
+#[test]
+fn decode_multi() {
+	let multi_point = GeogMultiPoint::from_ewkb(&hex(MULTI_POINT_BE)).unwrap();
+	assert_eq!(multi_point.points.len(), 2);
+	assert_eq!(multi_point.srid, Some(4326));
+	let multi_line = GeogMultiLineString::from_ewkb(&hex(MULTI_LINE_STRING)).unwrap();
+	assert_eq!(multi_line.to_string(), "SRID=4326;MULTILINESTRING((1 2,3 4),(3 4,1 2))");
+	let collection = GeogGeometryCollection::from_ewkb(&hex(COLLECTION)).unwrap();
+	assert_eq!(collection.geometries.len(), 2);
+	assert_eq!(collection.srid, Some(4326));
+
+	assert_eq!(multi_line.to_ewkb(), hex(MULTI_LINE_STRING));
+	assert_eq!(collection.to_ewkb(), hex(COLLECTION));
+	assert!(GeogMultiPoint::from_ewkb(&hex(COLLECTION)).is_err());
+}
+
+#[test]
+fn encode() {
+	assert_eq!(point(None).to_ewkb(), hex(POINT_LE));
+	assert_eq!(point(Some(4326)).to_ewkb(), hex(POINT_SRID_LE));
+	assert_eq!(GeogPointZ { x: 1.0, y: 2.0, z: 3.0, srid: Some(4326) }.to_ewkb(), hex(POINT_Z));
+	assert_eq!(GeogPointM { x: 1.0, y: 2.0, m: 4.0, srid: Some(4326) }.to_ewkb(), hex(POINT_M));
+	assert_eq!(GeogPointZM { x: 1.0, y: 2.0, z: 3.0, m: 4.0, srid: Some(4326) }.to_ewkb(), hex(POINT_ZM));
+	for blob in &[LINE_STRING, POLYGON, COLLECTION] {
+		assert_eq!(GeogAny::from_ewkb(&hex(blob)).unwrap().to_ewkb(), hex(blob));
+	}
+}
+
+#[test]
+fn truncated() {
+	for blob in &[POINT_SRID_BE, LINE_STRING, POLYGON, MULTI_POINT_BE, MULTI_LINE_STRING, COLLECTION] {
+		let bytes = hex(blob);
+		for len in 0..bytes.len() {
+			assert_eq!(GeogAny::from_ewkb(&bytes[..len]), Err(GeographyError::Truncated { offset: len }));
+		}
+	}
+	let bytes = hex(POINT_ZM);
+	for len in 0..bytes.len() {
+		assert_eq!(GeogPointZM::from_ewkb(&bytes[..len]), Err(GeographyError::Truncated { offset: len }));
+	}
+}
+
+#[test]
+fn trailing_bytes() {
+	for blob in &[POINT_SRID_LE, LINE_STRING, POLYGON, MULTI_POINT_BE, COLLECTION] {
+		let mut bytes = hex(blob);
+		let len = bytes.len();
+		bytes.push(0);
+		let trailing = GeographyError::TrailingBytes { offset: len };
+		assert_eq!(GeogAny::from_ewkb(&bytes), Err(trailing.clone()));
+		assert_eq!(EwkbView::new(&bytes).unwrap_err(), trailing);
+		assert_eq!(GeographyRef::from_ewkb(&bytes).unwrap_err(), trailing);
+	}
+	let mut bytes = hex(POINT_SRID_LE);
+	bytes.push(0);
+	assert_eq!(GeogPoint::from_ewkb(&bytes), Err(GeographyError::TrailingBytes { offset: 25 }));
+	let mut bytes = hex(POINT_ZM);
+	bytes.extend_from_slice(&hex(POINT_ZM));
+	assert_eq!(GeogPointZM::from_ewkb(&bytes), Err(GeographyError::TrailingBytes { offset: 41 }));
+	assert_eq!(
+		GeogPoint::from_hex_ewkb("0101000020E6100000000000000000F03F000000000000004000"),
+		Err(GeographyError::TrailingBytes { offset: 25 })
+	);
+}
+
+#[test]
+fn unexpected_type() {
+	assert_eq!(
+		GeogPoint::from_ewkb(&hex(LINE_STRING)),
+		Err(GeographyError::UnexpectedType {
+			expected: GeometryType::Point,
+			found: GeometryType::LineString,
+			srid: Some(4326),
+		})
+	);
+	assert_eq!(
+		GeogPoint::from_ewkb(&hex(LINE_STRING)).unwrap_err().to_string(),
+		"expected a Point, found a LineString with SRID 4326"
+	);
+	// A multipolygon holding a point.
+	let bytes = hex("01 06000000 01000000 01 01000000 000000000000F03F 0000000000000040");
+	assert_eq!(
+		GeogAny::from_ewkb(&bytes),
+		Err(GeographyError::UnexpectedType { expected: GeometryType::Polygon, found: GeometryType::Point, srid: None })
+	);
+}
+
+#[test]
+fn malformed() {
+	assert_eq!(
+		GeogPoint::from_ewkb(&hex("02 01000000 000000000000F03F 0000000000000040")),
+		Err(GeographyError::InvalidByteOrder(2))
+	);
+	assert_eq!(GeogAny::from_ewkb(&hex("01 08000000 00000000")), Err(GeographyError::UnknownType(8)));
+	// Only the flag bits are masked off: 0x101 is not a point, and ISO Z codes are not supported.
+	assert_eq!(
+		GeogAny::from_ewkb(&hex("01 01010000 000000000000F03F 0000000000000040")),
+		Err(GeographyError::UnknownType(0x101))
+	);
+	assert_eq!(
+		GeogAny::from_ewkb(&hex("01 E9030000 000000000000F03F 0000000000000040 0000000000000840")),
+		Err(GeographyError::UnknownType(1001))
+	);
+	assert_eq!(
+		GeogPoint::from_ewkb(&hex("01 01000020 FFFFFFFF 000000000000F03F 0000000000000040")),
+		Err(GeographyError::InvalidSrid(-1))
+	);
+	// A linestring claiming 2^32 - 1 points.
+	let bytes = hex("01 02000000 FFFFFFFF 000000000000F03F");
+	assert_eq!(GeogLineString::from_ewkb(&bytes), Err(GeographyError::Truncated { offset: bytes.len() }));
+	// Collections nested far deeper than any real geometry.
+	let nested = hex(&"01 07000000 01000000".repeat(10_000));
+	assert_eq!(GeogAny::from_ewkb(&nested), Err(GeographyError::TooDeeplyNested));
+}
+
+#[test]
+fn hex_ewkb() {
+	let text = "0101000020E6100000000000000000F03F0000000000000040";
+	assert_eq!(GeogPoint::from_hex_ewkb(text).unwrap(), point(Some(4326)));
+	assert_eq!(GeogPoint::from_hex_ewkb(&text.to_lowercase()).unwrap(), point(Some(4326)));
+	assert_eq!(point(Some(4326)).to_hex_ewkb(), text);
+	let collection = GeogAny::from_ewkb(&hex(COLLECTION)).unwrap();
+	assert_eq!(GeogAny::from_hex_ewkb(&collection.to_hex_ewkb()).unwrap(), collection);
+
+	assert_eq!(GeogPoint::from_hex_ewkb("0101000020E610000"), Err(GeographyError::InvalidHex { offset: 17 }));
+	assert_eq!(GeogPoint::from_hex_ewkb("01010000 0E6100000"), Err(GeographyError::InvalidHex { offset: 8 }));
+	assert_eq!(GeogPoint::from_hex_ewkb(""), Err(GeographyError::Truncated { offset: 0 }));
+}
+
+fn coords(view: &EwkbView) -> Vec<(f64, f64)> {
+	view.points().map(|p| (p.x, p.y)).collect()
+}
+
+#[test]
+fn view() {
+	let bytes = hex(POINT_ZM);
+	let view = EwkbView::new(&bytes).unwrap();
+	assert_eq!(view.geometry_type(), GeometryType::Point);
+	assert_eq!(view.srid(), Some(4326));
+	assert_eq!(view.dimension(), Dimension::Xyzm);
+	assert_eq!(view.points().collect::<Vec<_>>(), vec![point(Some(4326))]);
+
+	let bytes = hex(POLYGON);
+	let view = EwkbView::new(&bytes).unwrap();
+	assert_eq!(view.geometry_type(), GeometryType::Polygon);
+	assert_eq!(coords(&view), vec![(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (0.0, 0.0)]);
+
+	let bytes = hex(MULTI_POINT_BE);
+	assert_eq!(coords(&EwkbView::new(&bytes).unwrap()), vec![(1.0, 2.0), (3.0, 4.0)]);
+	let bytes = hex(COLLECTION);
+	assert_eq!(coords(&EwkbView::new(&bytes).unwrap()), vec![(1.0, 2.0), (1.0, 2.0), (3.0, 4.0)]);
+
+	// Stepping with `next` and folding the rest give the same points.
+	let view = EwkbView::new(&bytes).unwrap();
+	let mut points = view.points();
+	let first = points.next().unwrap();
+	let second = points.next().unwrap();
+	let rest: Vec<_> = points.map(|p| (p.x, p.y)).collect();
+	assert_eq!(vec![(first.x, first.y), (second.x, second.y)], coords(&view)[..2].to_vec());
+	assert_eq!(rest, vec![(3.0, 4.0)]);
+	assert_eq!(view.points().count(), 3);
+
+	// Empty parts are skipped.
+	let bytes = hex("01 07000000 03000000 01 03000000 00000000 01 07000000 00000000
+		01 02000000 01000000 000000000000F03F 0000000000000040");
+	assert_eq!(coords(&EwkbView::new(&bytes).unwrap()), vec![(1.0, 2.0)]);
+
+	let bytes = hex(LINE_STRING);
+	assert_eq!(EwkbView::new(&bytes[..bytes.len() - 1]).unwrap_err(), GeographyError::Truncated {
+		offset: bytes.len() - 1
+	});
+	let nested = hex(&"01 07000000 01000000".repeat(10_000));
+	assert_eq!(EwkbView::new(&nested).unwrap_err(), GeographyError::TooDeeplyNested);
+}
+
+#[test]
+fn rings_and_bbox() {
+	let bytes = hex(MULTI_LINE_STRING);
+	let view = EwkbView::new(&bytes).unwrap();
+	let rings: Vec<Vec<_>> = view.rings().map(|r| r.map(|p| (p.x, p.y)).collect()).collect();
+	assert_eq!(rings, vec![vec![(1.0, 2.0), (3.0, 4.0)], vec![(3.0, 4.0), (1.0, 2.0)]]);
+	assert_eq!(view.bbox(), Some(GeogBox { xmin: 1.0, ymin: 2.0, xmax: 3.0, ymax: 4.0 }));
+
+	// The point of a collection is not a ring.
+	let bytes = hex(COLLECTION);
+	let view = EwkbView::new(&bytes).unwrap();
+	assert_eq!(view.rings().map(Iterator::count).collect::<Vec<_>>(), vec![2]);
+	assert_eq!(EwkbView::new(&hex(MULTI_POINT_BE)).unwrap().rings().count(), 0);
+	assert_eq!(EwkbView::new(&hex("01 07000000 00000000")).unwrap().bbox(), None);
+
+	let g = GeographyRef::from_ewkb(&hex(POLYGON)).unwrap();
+	assert_eq!(g.geometry_type(), GeometryType::Polygon);
+	assert_eq!(g.srid(), Some(4326));
+	assert_eq!(g.rings().count(), 1);
+	assert_eq!(g.bbox(), Some(GeogBox { xmin: 0.0, ymin: 0.0, xmax: 1.0, ymax: 1.0 }));
+	assert_eq!(g.decode::<GeogPolygon>().unwrap().to_string(), "SRID=4326;POLYGON((0 0,1 0,0 1,0 0))");
+	assert!(g.decode::<GeogLineString>().is_err());
+	let z = GeographyRef::from_ewkb(&hex(POINT_Z)).unwrap();
+	assert_eq!(z.points().count(), 1);
+	assert_eq!(
+		z.decode::<GeogAny>(),
+		Err(GeographyError::WrongDimension { expected: Dimension::Xy, found: Dimension::Xyz })
+	);
+	assert!(GeographyRef::from_ewkb(&hex(POLYGON)[..20]).is_err());
+}
+
+#[test]
+fn raw() {
+	let raw = Ewkb(hex(MULTI_POINT_BE));
+	assert_eq!(raw.geometry_type(), Ok(GeometryType::MultiPoint));
+	assert_eq!(raw.srid(), Ok(Some(4326)));
+	assert_eq!(raw.to_ewkb(), hex(MULTI_POINT_BE));
+	assert_eq!(Ewkb(hex(POINT_LE)).srid(), Ok(None));
+
+	// Only the header is read.
+	let truncated = Ewkb(hex(LINE_STRING)[..20].to_vec());
+	assert_eq!(truncated.geometry_type(), Ok(GeometryType::LineString));
+	assert!(truncated.view().is_err());
+	assert_eq!(Ewkb(vec![]).srid(), Err(GeographyError::Truncated { offset: 0 }));
+
+	let g = GeographyRef::from_ewkb(&hex(POLYGON)).unwrap();
+	assert_eq!(Ewkb::from(g).0, hex(POLYGON));
+}
@@ -0,0 +1,95 @@
+//! Conversions to and from `geo-types` with the `geo-types` feature.
+
+#![cfg(feature = "geo-types")]
+
+extern crate diesel_geography;
+extern crate geo_types;
+
+use std::convert::TryFrom;
+use diesel_geography::types::*;
+use geo_types::{coord, line_string, point, polygon, Geometry, Line, LineString, MultiPoint, Polygon, Rect};
+
+fn pt(x: f64, y: f64) -> GeogPoint {
+	GeogPoint { x, y, srid: None }
+}
+
+#[test]
+fn points() {
+	let p = GeogPoint { x: 13.4, y: 52.5, srid: Some(4326) };
+	assert_eq!(geo_types::Point::from(p), point!(x: 13.4, y: 52.5));
+	// The SRID is lost on the way.
+	assert_eq!(GeogPoint::from(point!(x: 13.4, y: 52.5)), pt(13.4, 52.5));
+
+	let multi = GeogMultiPoint { points: vec![pt(1.0, 2.0), pt(3.0, 4.0)], srid: Some(4326) };
+	let converted = MultiPoint::from(multi);
+	assert_eq!(converted, MultiPoint::new(vec![point!(x: 1.0, y: 2.0), point!(x: 3.0, y: 4.0)]));
+	assert_eq!(GeogMultiPoint::from(converted), GeogMultiPoint { points: vec![pt(1.0, 2.0), pt(3.0, 4.0)], srid: None });
+}
+
+#[test]
+fn lines() {
+	let line = GeogLineString { points: vec![pt(0.0, 0.0), pt(1.0, 1.0)], srid: Some(4326) };
+	let converted = LineString::from(line);
+	assert_eq!(converted, line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]);
+	assert_eq!(GeogLineString::from(converted), GeogLineString { points: vec![pt(0.0, 0.0), pt(1.0, 1.0)], srid: None });
+
+	let line = Geometry::Line(Line::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 2.0, y: 1.0 }));
+	assert_eq!(
+		GeogAny::from(line),
+		GeogAny::LineString(GeogLineString { points: vec![pt(0.0, 0.0), pt(2.0, 1.0)], srid: None })
+	);
+}
+
+#[test]
+fn polygons() {
+	let closed = GeogPolygon {
+		rings: vec![
+			vec![pt(0.0, 0.0), pt(4.0, 0.0), pt(4.0, 4.0), pt(0.0, 0.0)],
+			vec![pt(1.0, 1.0), pt(2.0, 1.0), pt(1.0, 2.0), pt(1.0, 1.0)],
+		],
+		srid: Some(4326),
+	};
+	let converted = Polygon::from(closed.clone());
+	assert_eq!(converted.exterior().0.len(), 4);
+	assert_eq!(converted.interiors().len(), 1);
+	assert_eq!(GeogPolygon::from(converted), GeogPolygon { srid: None, ..closed });
+
+	// geo-types closes open rings.
+	let open = GeogPolygon { rings: vec![vec![pt(0.0, 0.0), pt(1.0, 0.0), pt(0.0, 1.0)]], srid: None };
+	let converted = Polygon::from(open);
+	assert_eq!(converted, polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 0.0, y: 1.0), (x: 0.0, y: 0.0)]);
+	assert_eq!(GeogPolygon::from(converted).rings[0], vec![pt(0.0, 0.0), pt(1.0, 0.0), pt(0.0, 1.0), pt(0.0, 0.0)]);
+
+	// Empty polygons stay empty.
+	let empty = GeogPolygon { rings: vec![], srid: None };
+	assert_eq!(GeogPolygon::from(Polygon::from(empty.clone())), empty);
+
+	let rect = Geometry::Rect(Rect::new(coord! { x: 0.0, y: 0.0 }, coord! { x: 1.0, y: 2.0 }));
+	match GeogAny::from(rect) {
+		GeogAny::Polygon(p) => assert_eq!(p.rings[0].len(), 5),
+		g => panic!("expected a polygon, found {}", g),
+	}
+}
+
+#[test]
+fn collections() {
+	let collection = GeogGeometryCollection {
+		geometries: vec![
+			GeogAny::Point(pt(1.0, 2.0)),
+			GeogAny::MultiLineString(GeogMultiLineString {
+				lines: vec![GeogLineString { points: vec![pt(0.0, 0.0), pt(1.0, 1.0)], srid: None }],
+				srid: None,
+			}),
+		],
+		srid: None,
+	};
+	let converted = Geometry::from(GeogAny::GeometryCollection(collection.clone()));
+	assert_eq!(GeogGeometryCollection::try_from(converted), Ok(collection));
+}
+
+#[test]
+fn wrong_kind() {
+	let line = Geometry::LineString(line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)]);
+	let any = GeogAny::from(line.clone());
+	assert_eq!(GeogPoint::try_from(line), Err(any));
+}
@@ -0,0 +1,103 @@
+//! Client-side distances and bearings against published reference values.
+
+extern crate diesel_geography;
+
+use diesel_geography::types::GeogPoint;
+
+fn pt(x: f64, y: f64) -> GeogPoint {
+	GeogPoint { x, y, srid: Some(4326) }
+}
+
+fn dms(degrees: f64, minutes: f64, seconds: f64) -> f64 {
+	degrees.signum() * (degrees.abs() + minutes / 60.0 + seconds / 3600.0)
+}
+
+/// One millimeter, the tolerance for distances.
+const MM: f64 = 1e-3;
+/// 0.01 arc seconds in radians, the precision of the reference bearings.
+const ARC_CENTISECOND: f64 = 0.01 / 3600.0 * std::f64::consts::PI / 180.0;
+
+/// Flinders Peak and Buninyong, the worked example in Vincenty (1975).
+fn flinders_peak() -> GeogPoint {
+	pt(dms(144.0, 25.0, 29.5244), dms(-37.0, 57.0, 3.7203))
+}
+
+fn buninyong() -> GeogPoint {
+	pt(dms(143.0, 55.0, 35.3839), dms(-37.0, 39.0, 10.1561))
+}
+
+#[test]
+fn vincenty_reference() {
+	let (from, to) = (flinders_peak(), buninyong());
+	let distance = from.vincenty_distance(&to).unwrap();
+	assert!((distance - 54972.271).abs() < MM, "{}", distance);
+	assert!((to.vincenty_distance(&from).unwrap() - distance).abs() < MM);
+
+	let bearing = from.initial_bearing(&to).unwrap();
+	let expected = dms(306.0, 52.0, 5.37).to_radians();
+	assert!((bearing - expected).abs() < ARC_CENTISECOND, "{}", bearing.to_degrees());
+	let back = to.initial_bearing(&from).unwrap();
+	let expected = dms(127.0, 10.0, 25.07).to_radians();
+	assert!((back - expected).abs() < ARC_CENTISECOND, "{}", back.to_degrees());
+
+	let reached = from.destination(dms(306.0, 52.0, 5.37).to_radians(), 54972.271);
+	assert!(reached.vincenty_distance(&to).unwrap() < 0.01, "{:?}", reached);
+	assert_eq!(reached.srid, Some(4326));
+}
+
+#[test]
+fn haversine() {
+	// One degree of longitude on the equator of a sphere with the WGS84 mean radius.
+	let distance = pt(0.0, 0.0).haversine_distance(&pt(1.0, 0.0));
+	assert!((distance - 111195.080).abs() < MM, "{}", distance);
+	// Within 0.5% of the spheroidal distance.
+	let (from, to) = (flinders_peak(), buninyong());
+	let ratio = from.haversine_distance(&to) / from.vincenty_distance(&to).unwrap();
+	assert!((ratio - 1.0).abs() < 0.005, "{}", ratio);
+}
+
+#[test]
+fn identical_points() {
+	let p = flinders_peak();
+	assert_eq!(p.vincenty_distance(&p), Some(0.0));
+	assert_eq!(p.haversine_distance(&p), 0.0);
+	// Like ST_Azimuth, there is no bearing between identical points.
+	assert_eq!(p.initial_bearing(&p), None);
+	let reached = p.destination(1.0, 0.0);
+	assert!((reached.x - p.x).abs() < 1e-12 && (reached.y - p.y).abs() < 1e-12, "{:?}", reached);
+}
+
+#[test]
+fn antipodal() {
+	// Vincenty's formula does not converge for nearly antipodal points.
+	let (from, to) = (pt(0.0, 0.0), pt(179.7, 0.1));
+	assert_eq!(from.vincenty_distance(&to), None);
+	assert_eq!(from.initial_bearing(&to), None);
+	// The spherical distance still works, at about half the circumference.
+	let distance = from.haversine_distance(&to);
+	assert!(distance > 19_900_000.0 && distance < 20_100_000.0, "{}", distance);
+}
+
+#[test]
+fn antimeridian() {
+	// One degree of longitude on the equator of the WGS84 ellipsoid, across ±180°.
+	let (west, east) = (pt(179.5, 0.0), pt(-179.5, 0.0));
+	let degree = 111319.491;
+	assert!((west.vincenty_distance(&east).unwrap() - degree).abs() < MM);
+	let bearing = west.initial_bearing(&east).unwrap();
+	assert!((bearing - 90f64.to_radians()).abs() < 1e-9, "{}", bearing.to_degrees());
+	let bearing = east.initial_bearing(&west).unwrap();
+	assert!((bearing - 270f64.to_radians()).abs() < 1e-9, "{}", bearing.to_degrees());
+
+	// The longitude of the destination is normalized.
+	let reached = west.destination(90f64.to_radians(), degree);
+	assert!((reached.x + 179.5).abs() < 1e-8 && reached.y.abs() < 1e-8, "{:?}", reached);
+	let reached = east.destination(270f64.to_radians(), degree);
+	assert!((reached.x - 179.5).abs() < 1e-8 && reached.y.abs() < 1e-8, "{:?}", reached);
+
+	// Across the antimeridian away from the equator.
+	let (fiji, samoa) = (pt(178.44, -18.14), pt(-171.76, -13.83));
+	let direct = fiji.vincenty_distance(&samoa).unwrap();
+	assert!(direct < 1_200_000.0, "{}", direct);
+	assert!((fiji.haversine_distance(&samoa) / direct - 1.0).abs() < 0.005);
+}
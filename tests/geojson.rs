@@ -0,0 +1,102 @@
+//! GeoJSON serialization with the `serde` feature.
+
+#![cfg(feature = "serde")]
+
+extern crate diesel_geography;
+#[macro_use]
+extern crate serde;
+extern crate serde_json;
+
+use diesel_geography::types::*;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Store {
+	#[serde(with = "diesel_geography::geojson")]
+	location: GeogPoint,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Region {
+	#[serde(with = "diesel_geography::geojson")]
+	shape: GeogAny,
+}
+
+fn pt(x: f64, y: f64) -> GeogPoint {
+	GeogPoint { x, y, srid: Some(4326) }
+}
+
+#[test]
+fn point() {
+	let store = Store { location: pt(13.4, 52.5) };
+	let json = serde_json::to_string(&store).unwrap();
+	assert_eq!(json, r#"{"location":{"type":"Point","coordinates":[13.4,52.5]}}"#);
+	assert_eq!(serde_json::from_str::<Store>(&json).unwrap(), store);
+}
+
+#[test]
+fn geometries() {
+	let square = GeogPolygon { rings: vec![vec![pt(0.0, 0.0), pt(1.0, 0.0), pt(1.0, 1.0), pt(0.0, 0.0)]], srid: Some(4326) };
+	let line = GeogLineString { points: vec![pt(0.0, 0.0), pt(2.0, 1.0)], srid: Some(4326) };
+	let cases = vec![
+		(GeogAny::LineString(line.clone()), r#"{"type":"LineString","coordinates":[[0.0,0.0],[2.0,1.0]]}"#),
+		(GeogAny::Polygon(square.clone()), r#"{"type":"Polygon","coordinates":[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]]}"#),
+		(
+			GeogAny::MultiPoint(GeogMultiPoint { points: vec![pt(1.0, 2.0), pt(3.0, 4.0)], srid: Some(4326) }),
+			r#"{"type":"MultiPoint","coordinates":[[1.0,2.0],[3.0,4.0]]}"#,
+		),
+		(
+			GeogAny::MultiLineString(GeogMultiLineString { lines: vec![line.clone()], srid: Some(4326) }),
+			r#"{"type":"MultiLineString","coordinates":[[[0.0,0.0],[2.0,1.0]]]}"#,
+		),
+		(
+			GeogAny::MultiPolygon(GeogMultiPolygon { polygons: vec![square.clone()], srid: Some(4326) }),
+			r#"{"type":"MultiPolygon","coordinates":[[[[0.0,0.0],[1.0,0.0],[1.0,1.0],[0.0,0.0]]]]}"#,
+		),
+		(
+			GeogAny::GeometryCollection(GeogGeometryCollection {
+				geometries: vec![GeogAny::Point(pt(1.0, 2.0)), GeogAny::LineString(line)],
+				srid: Some(4326),
+			}),
+			r#"{"type":"GeometryCollection","geometries":[{"type":"Point","coordinates":[1.0,2.0]},{"type":"LineString","coordinates":[[0.0,0.0],[2.0,1.0]]}]}"#,
+		),
+	];
+	for (shape, object) in cases {
+		let region = Region { shape };
+		let json = serde_json::to_string(&region).unwrap();
+		assert_eq!(json, format!(r#"{{"shape":{}}}"#, object));
+		assert_eq!(serde_json::from_str::<Region>(&json).unwrap(), region);
+	}
+}
+
+#[test]
+fn srid_and_altitude() {
+	// GeoJSON is always WGS84: the SRID is not written and 4326 is assumed when reading.
+	let store = Store { location: GeogPoint { x: 13.4, y: 52.5, srid: Some(4258) } };
+	assert_eq!(serde_json::to_string(&store).unwrap(), r#"{"location":{"type":"Point","coordinates":[13.4,52.5]}}"#);
+	let loaded: Store = serde_json::from_str(r#"{"location":{"type":"Point","coordinates":[13.4,52.5,34.0]}}"#).unwrap();
+	assert_eq!(loaded.location, pt(13.4, 52.5));
+
+	// Projected coordinates would be misread as longitudes and latitudes.
+	let store = Store { location: GeogPoint { x: 1_492_000.0, y: 6_894_000.0, srid: Some(3857) } };
+	let err = serde_json::to_string(&store).unwrap_err();
+	assert!(err.to_string().contains("SRID 3857 is not geographic"), "{}", err);
+	let region = Region { shape: GeogAny::Point(GeogPoint { x: 0.0, y: 0.0, srid: Some(3857) }) };
+	assert!(serde_json::to_string(&region).is_err());
+	// Without an SRID the coordinates are assumed to be WGS84.
+	let store = Store { location: GeogPoint { x: 13.4, y: 52.5, srid: None } };
+	assert_eq!(serde_json::to_string(&store).unwrap(), r#"{"location":{"type":"Point","coordinates":[13.4,52.5]}}"#);
+}
+
+#[test]
+fn errors() {
+	let err = serde_json::from_str::<Store>(r#"{"location":{"type":"LineString","coordinates":[[0,0],[1,1]]}}"#).unwrap_err();
+	assert!(err.to_string().contains("unexpected GeoJSON type LineString"), "{}", err);
+
+	let err = serde_json::from_str::<Store>(r#"{"location":{"type":"Point","coordinates":[13.4]}}"#).unwrap_err();
+	assert!(err.to_string().contains("a position needs at least 2 elements, found 1"), "{}", err);
+	let err = serde_json::from_str::<Region>(r#"{"shape":{"type":"LineString","coordinates":[[0,0],[]]}}"#).unwrap_err();
+	assert!(err.to_string().contains("a position needs at least 2 elements, found 0"), "{}", err);
+
+	let err = serde_json::from_str::<Region>(r#"{"shape":{"type":"Circle","coordinates":[0,0]}}"#).unwrap_err();
+	assert!(err.to_string().contains("unknown variant `Circle`"), "{}", err);
+}
@@ -0,0 +1,63 @@
+//! Constructing validated points, without a database.
+
+extern crate diesel_geography;
+
+use diesel_geography::error::CoordinateError;
+use diesel_geography::types::*;
+
+#[test]
+fn wgs84() {
+	assert_eq!(GeogPoint::wgs84(13.4, 52.5), Ok(GeogPoint { x: 13.4, y: 52.5, srid: Some(4326) }));
+	// The bounds are inclusive.
+	assert!(GeogPoint::wgs84(-180.0, -90.0).is_ok());
+	assert!(GeogPoint::wgs84(180.0, 90.0).is_ok());
+}
+
+#[test]
+fn ranges() {
+	assert_eq!(GeogPoint::wgs84(180.5, 0.0), Err(CoordinateError::InvalidLongitude(180.5)));
+	assert_eq!(GeogPoint::wgs84(-181.0, 0.0), Err(CoordinateError::InvalidLongitude(-181.0)));
+	assert_eq!(GeogPoint::wgs84(0.0, 90.1), Err(CoordinateError::InvalidLatitude(90.1)));
+	assert_eq!(GeogPoint::wgs84(0.0, -91.0), Err(CoordinateError::InvalidLatitude(-91.0)));
+	// Swapped coordinates are a common mistake.
+	assert_eq!(GeogPoint::wgs84(52.5, 113.4), Err(CoordinateError::InvalidLatitude(113.4)));
+	assert_eq!(GeogPoint::wgs84(f64::INFINITY, 0.0), Err(CoordinateError::InvalidLongitude(f64::INFINITY)));
+	assert_eq!(GeogPoint::wgs84(0.0, f64::NEG_INFINITY), Err(CoordinateError::InvalidLatitude(f64::NEG_INFINITY)));
+}
+
+#[test]
+fn nan() {
+	// NaN != NaN, so match instead of comparing.
+	assert!(matches!(GeogPoint::wgs84(f64::NAN, 0.0), Err(CoordinateError::InvalidLongitude(lon)) if lon.is_nan()));
+	assert!(matches!(GeogPoint::wgs84(0.0, f64::NAN), Err(CoordinateError::InvalidLatitude(lat)) if lat.is_nan()));
+	assert!(GeogPoint::builder(f64::NAN, f64::NAN).srid_unchecked(4979).build().is_err());
+}
+
+#[test]
+fn srids() {
+	assert_eq!(GeogPoint::builder(-77.0, 38.9).srid(4269).build().unwrap().srid, Some(4269));
+	for &srid in GEOGRAPHIC_SRIDS {
+		assert_eq!(GeogPoint::builder(0.0, 0.0).srid(srid).build().unwrap().srid, Some(srid));
+	}
+	// Projected and unknown SRIDs are rejected.
+	assert_eq!(GeogPoint::builder(0.0, 0.0).srid(3857).build(), Err(CoordinateError::NonGeographicSrid(3857)));
+	assert_eq!(GeogPoint::builder(0.0, 0.0).srid(0).build(), Err(CoordinateError::NonGeographicSrid(0)));
+	assert_eq!(GeogPoint::builder(0.0, 0.0).srid(4979).build(), Err(CoordinateError::NonGeographicSrid(4979)));
+	// The range is still checked first.
+	assert_eq!(GeogPoint::builder(200.0, 0.0).srid(3857).build(), Err(CoordinateError::InvalidLongitude(200.0)));
+}
+
+#[test]
+fn unchecked_srids() {
+	let p = GeogPoint::builder(13.4, 52.5).srid_unchecked(4979).build();
+	assert_eq!(p, Ok(GeogPoint { x: 13.4, y: 52.5, srid: Some(4979) }));
+	assert_eq!(
+		GeogPoint::builder(200.0, 52.5).srid_unchecked(4979).build(),
+		Err(CoordinateError::InvalidLongitude(200.0))
+	);
+	// A later `srid` turns the check back on.
+	assert_eq!(
+		GeogPoint::builder(0.0, 0.0).srid_unchecked(4979).srid(3857).build(),
+		Err(CoordinateError::NonGeographicSrid(3857))
+	);
+}
@@ -0,0 +1,579 @@
+//! Integration tests against a PostGIS database.
+//!
+//! The tests are ignored by default. Set `DATABASE_URL` to a database with the `postgis` extension
+//! installed and run them with `--ignored`, e.g.
+//! `DATABASE_URL=postgres://localhost/diesel_geography_test cargo test -- --ignored`.
+//! Every test runs in a transaction that is rolled back, using temporary tables.
+
+extern crate diesel;
+extern crate diesel_geography;
+
+use std::env;
+use std::error::Error;
+use diesel::connection::SimpleConnection;
+use diesel::dsl::sql;
+use diesel::prelude::*;
+use diesel::sql_types::{Array, Nullable};
+use diesel_geography::error::{GeographyError, SridMismatch};
+use diesel_geography::ewkb::{FromEwkb, GeometryType, ToEwkb};
+use diesel_geography::expression_methods::*;
+use diesel_geography::functions::*;
+use diesel_geography::sql_types::*;
+use diesel_geography::types::*;
+use diesel_geography::wkt::WktError;
+
+table! {
+	use diesel::sql_types::*;
+	use diesel_geography::sql_types::*;
+
+	shapes (id) {
+		id -> Int4,
+		g -> Geography,
+	}
+}
+
+table! {
+	use diesel::sql_types::*;
+	use diesel_geography::sql_types::*;
+
+	places (id) {
+		id -> Int4,
+		location -> Nullable<Geography>,
+		waypoints -> Nullable<Array<Geography>>,
+	}
+}
+
+table! {
+	use diesel::sql_types::*;
+	use diesel_geography::sql_types::*;
+
+	typed_places (id) {
+		id -> Int4,
+		location -> TypedGeography<Srid4326>,
+	}
+}
+
+table! {
+	use diesel::sql_types::*;
+	use diesel_geography::sql_types::*;
+
+	planar (id) {
+		id -> Int4,
+		g -> Geometry,
+	}
+}
+
+const SCHEMA: &str = "
+	CREATE TEMP TABLE shapes (id SERIAL PRIMARY KEY, g GEOGRAPHY NOT NULL);
+	CREATE TEMP TABLE places (id SERIAL PRIMARY KEY, location GEOGRAPHY(POINT, 4326), waypoints GEOGRAPHY[]);
+	CREATE TEMP TABLE typed_places (id SERIAL PRIMARY KEY, location GEOGRAPHY(POINT, 4326) NOT NULL);
+	CREATE TEMP TABLE planar (id SERIAL PRIMARY KEY, g GEOMETRY NOT NULL);
+";
+
+/// The `GeographyError` that made loading a value fail, if any.
+fn geography_error<T: std::fmt::Debug>(result: QueryResult<T>) -> Option<GeographyError> {
+	let err = match result {
+		Err(diesel::result::Error::DeserializationError(e)) => e,
+		other => panic!("expected a deserialization error, found {:?}", other),
+	};
+	// Diesel wraps the error with the name of the field.
+	let mut source: Option<&(dyn Error + 'static)> = Some(&*err);
+	while let Some(e) = source {
+		if let Some(e) = e.downcast_ref::<GeographyError>() {
+			return Some(e.clone());
+		}
+		source = e.source();
+	}
+	None
+}
+
+/// One millimeter, the tolerance for distances.
+const MM: f64 = 1e-3;
+
+fn connection() -> PgConnection {
+	let url = env::var("DATABASE_URL").expect("DATABASE_URL must point to a database with PostGIS");
+	let mut conn = PgConnection::establish(&url).expect("failed to connect to DATABASE_URL");
+	conn.begin_test_transaction().unwrap();
+	conn.batch_execute(SCHEMA).unwrap();
+	conn
+}
+
+/// Inserts the value into `shapes`, reads it back and compares it to the original.
+///
+/// The values are compared as EWKT, as EWKB only carries the SRID of the outermost geometry.
+macro_rules! assert_round_trip {
+	($conn:expr, $t:ty, $value:expr) => {{
+		let value: $t = $value;
+		diesel::delete(shapes::table).execute($conn).unwrap();
+		diesel::insert_into(shapes::table).values(shapes::g.eq(value.clone())).execute($conn).unwrap();
+		let loaded: $t = shapes::table.select(shapes::g).first($conn).unwrap();
+		assert_eq!(loaded.to_string(), value.to_string());
+	}};
+}
+
+fn pt(x: f64, y: f64) -> GeogPoint {
+	GeogPoint { x, y, srid: Some(4326) }
+}
+
+fn line(coords: &[(f64, f64)]) -> GeogLineString {
+	GeogLineString { points: coords.iter().map(|&(x, y)| pt(x, y)).collect(), srid: Some(4326) }
+}
+
+/// A closed ring, with the first point repeated at the end.
+fn ring(coords: &[(f64, f64)]) -> Vec<GeogPoint> {
+	coords.iter().chain(coords.first()).map(|&(x, y)| pt(x, y)).collect()
+}
+
+fn square(x: f64, y: f64, size: f64) -> GeogPolygon {
+	GeogPolygon {
+		rings: vec![ring(&[(x, y), (x + size, y), (x + size, y + size), (x, y + size)])],
+		srid: Some(4326),
+	}
+}
+
+fn assert_close(a: f64, b: f64, tolerance: f64) {
+	assert!((a - b).abs() <= tolerance, "{} and {} differ by more than {}", a, b, tolerance);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn round_trip_points() {
+	let conn = &mut connection();
+	assert_round_trip!(conn, GeogPoint, pt(13.4, 52.5));
+	assert_round_trip!(conn, GeogPoint, pt(-180.0, -90.0));
+	assert_round_trip!(conn, GeogPointZ, GeogPointZ { x: 13.4, y: 52.5, z: 34.0, srid: Some(4326) });
+	assert_round_trip!(conn, GeogPointM, GeogPointM { x: 13.4, y: 52.5, m: 7.0, srid: Some(4326) });
+	assert_round_trip!(conn, GeogPointZM, GeogPointZM { x: 13.4, y: 52.5, z: 34.0, m: 7.0, srid: Some(4326) });
+
+	// The elevation and measure are not silently dropped.
+	let loaded: QueryResult<GeogPoint> = shapes::table.select(shapes::g).first(conn);
+	assert!(matches!(loaded, Err(diesel::result::Error::DeserializationError(_))));
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn round_trip_shapes() {
+	let conn = &mut connection();
+	assert_round_trip!(conn, GeogLineString, line(&[(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)]));
+	assert_round_trip!(conn, GeogPolygon, square(0.0, 0.0, 1.0));
+	assert_round_trip!(conn, GeogPolygon, GeogPolygon {
+		rings: vec![
+			ring(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]),
+			ring(&[(1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (2.0, 1.0)]),
+		],
+		srid: Some(4326),
+	});
+	assert_round_trip!(conn, GeogMultiPolygon, GeogMultiPolygon {
+		polygons: vec![square(0.0, 0.0, 1.0), square(5.0, 5.0, 1.0)],
+		srid: Some(4326),
+	});
+	assert_round_trip!(conn, GeogMultiPoint, GeogMultiPoint { points: vec![pt(1.0, 2.0), pt(3.0, 4.0)], srid: Some(4326) });
+	assert_round_trip!(conn, GeogMultiLineString, GeogMultiLineString {
+		lines: vec![line(&[(0.0, 0.0), (1.0, 1.0)]), line(&[(2.0, 2.0), (3.0, 3.0)])],
+		srid: Some(4326),
+	});
+	assert_round_trip!(conn, GeogGeometryCollection, GeogGeometryCollection {
+		geometries: vec![GeogAny::Point(pt(1.0, 2.0)), GeogAny::Polygon(square(0.0, 0.0, 1.0))],
+		srid: Some(4326),
+	});
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn round_trip_any() {
+	let conn = &mut connection();
+	let values = vec![
+		GeogAny::Point(pt(1.0, 2.0)),
+		GeogAny::LineString(line(&[(0.0, 0.0), (1.0, 1.0)])),
+		GeogAny::Polygon(square(0.0, 0.0, 1.0)),
+		GeogAny::MultiPoint(GeogMultiPoint { points: vec![pt(1.0, 2.0), pt(3.0, 4.0)], srid: Some(4326) }),
+		GeogAny::MultiLineString(GeogMultiLineString {
+			lines: vec![line(&[(0.0, 0.0), (1.0, 1.0)]), line(&[(2.0, 2.0), (3.0, 3.0)])],
+			srid: Some(4326),
+		}),
+		GeogAny::MultiPolygon(GeogMultiPolygon { polygons: vec![square(0.0, 0.0, 1.0)], srid: Some(4326) }),
+		GeogAny::GeometryCollection(GeogGeometryCollection {
+			geometries: vec![GeogAny::Point(pt(1.0, 2.0)), GeogAny::LineString(line(&[(0.0, 0.0), (1.0, 1.0)]))],
+			srid: Some(4326),
+		}),
+	];
+	for value in values {
+		assert_round_trip!(conn, GeogAny, value);
+	}
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn lazy_loading() {
+	let conn = &mut connection();
+	let polygon = GeogPolygon {
+		rings: vec![
+			ring(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]),
+			ring(&[(1.0, 1.0), (1.0, 2.0), (2.0, 2.0), (2.0, 1.0)]),
+		],
+		srid: Some(4326),
+	};
+	diesel::insert_into(shapes::table).values(shapes::g.eq(polygon.clone())).execute(conn).unwrap();
+	let loaded: GeographyRef = shapes::table.select(shapes::g).first(conn).unwrap();
+	assert_eq!(loaded.geometry_type(), GeometryType::Polygon);
+	assert_eq!(loaded.srid(), Some(4326));
+	assert_eq!(loaded.rings().map(Iterator::count).collect::<Vec<_>>(), vec![5, 5]);
+	assert_eq!(loaded.bbox(), Some(GeogBox { xmin: 0.0, ymin: 0.0, xmax: 4.0, ymax: 4.0 }));
+	assert_eq!(loaded.decode::<GeogPolygon>().unwrap().to_string(), polygon.to_string());
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn raw_passthrough() {
+	let conn = &mut connection();
+	diesel::insert_into(shapes::table).values(shapes::g.eq(square(0.0, 0.0, 1.0))).execute(conn).unwrap();
+	let raw: Ewkb = shapes::table.select(shapes::g).first(conn).unwrap();
+	assert_eq!(raw.geometry_type(), Ok(GeometryType::Polygon));
+	assert_eq!(raw.srid(), Ok(Some(4326)));
+	assert_eq!(raw.0, square(0.0, 0.0, 1.0).to_ewkb());
+
+	diesel::insert_into(planar::table).values(planar::g.eq(&raw)).execute(conn).unwrap();
+	let copied: GeogPolygon = planar::table.select(planar::g).first(conn).unwrap();
+	assert_eq!(copied.to_string(), square(0.0, 0.0, 1.0).to_string());
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn srid() {
+	let conn = &mut connection();
+	let nad83 = GeogPoint { x: -77.0, y: 38.9, srid: Some(4269) };
+	diesel::insert_into(shapes::table).values(shapes::g.eq(nad83)).execute(conn).unwrap();
+	let (srid, loaded) = shapes::table.select((st_srid(shapes::g), shapes::g)).first::<(i32, GeogPoint)>(conn).unwrap();
+	assert_eq!(srid, 4269);
+	assert_eq!(loaded, nad83);
+
+	// PostGIS assigns 4326 to geography values without an SRID.
+	let unset = GeogPoint { x: 1.0, y: 2.0, srid: None };
+	diesel::delete(shapes::table).execute(conn).unwrap();
+	diesel::insert_into(shapes::table).values(shapes::g.eq(unset)).execute(conn).unwrap();
+	let loaded: GeogPoint = shapes::table.select(shapes::g).first(conn).unwrap();
+	assert_eq!(loaded.srid, Some(4326));
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn typed_srid() {
+	let conn = &mut connection();
+	let value = WithSrid::<GeogPoint, Srid4326>::new(pt(13.4, 52.5)).unwrap();
+	diesel::insert_into(typed_places::table).values(typed_places::location.eq(value)).execute(conn).unwrap();
+	let loaded: WithSrid<GeogPoint, Srid4326> = typed_places::table.select(typed_places::location).first(conn).unwrap();
+	assert_eq!(loaded, value);
+
+	let distance: f64 = typed_places::table
+		.select(st_distance(typed_places::location.as_geography(), pt(13.4, 52.5)))
+		.first(conn)
+		.unwrap();
+	assert_eq!(distance, 0.0);
+
+	// Rows with another SRID fail to load with a GeographyError.
+	let nad83 = sql::<TypedGeography<Srid4326>>("'SRID=4269;POINT(-77 38.9)'::geography");
+	let result = diesel::select(nad83).get_result::<WithSrid<GeogPoint, Srid4326>>(conn);
+	let expected = GeographyError::SridMismatch(SridMismatch { expected: 4326, found: Some(4269) });
+	assert_eq!(geography_error(result), Some(expected));
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn null() {
+	let conn = &mut connection();
+	diesel::insert_into(places::table)
+		.values((places::location.eq(None::<GeogPoint>), places::waypoints.eq(None::<Vec<GeogPoint>>)))
+		.execute(conn)
+		.unwrap();
+	let loaded: (Option<GeogPoint>, Option<Vec<GeogPoint>>) =
+		places::table.select((places::location, places::waypoints)).first(conn).unwrap();
+	assert_eq!(loaded, (None, None));
+
+	let azimuth: Option<f64> = diesel::select(st_azimuth(pt(1.0, 2.0), pt(1.0, 2.0))).get_result(conn).unwrap();
+	assert_eq!(azimuth, None);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn arrays() {
+	let conn = &mut connection();
+	let waypoints = vec![pt(1.0, 2.0), pt(3.0, 4.0)];
+	diesel::insert_into(places::table)
+		.values((places::location.eq(Some(pt(1.0, 2.0))), places::waypoints.eq(Some(waypoints.clone()))))
+		.execute(conn)
+		.unwrap();
+	let loaded: Option<Vec<GeogPoint>> = places::table.select(places::waypoints).first(conn).unwrap();
+	assert_eq!(loaded, Some(waypoints.clone()));
+
+	let found: i64 = places::table
+		.filter(places::location.eq_any(vec![pt(3.0, 4.0), pt(1.0, 2.0)]))
+		.count()
+		.get_result(conn)
+		.unwrap();
+	assert_eq!(found, 1);
+	let missing: i64 =
+		places::table.filter(places::location.eq_any(vec![pt(3.0, 4.0)])).count().get_result(conn).unwrap();
+	assert_eq!(missing, 0);
+
+	let values = vec![Some(GeogAny::Point(pt(1.0, 2.0))), None];
+	let loaded: Vec<Option<GeogAny>> =
+		diesel::select(values.clone().into_sql::<Array<Nullable<Geography>>>()).get_result(conn).unwrap();
+	assert_eq!(loaded, values);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn distance() {
+	let conn = &mut connection();
+	let (berlin, paris) = (pt(13.4, 52.5), pt(2.35, 48.86));
+	let (spheroid, sphere): (f64, f64) = diesel::select((
+		st_distance(berlin, paris),
+		st_distance_with_spheroid(berlin, paris, false),
+	))
+	.get_result(conn)
+	.unwrap();
+	assert_close(spheroid, berlin.vincenty_distance(&paris).unwrap(), MM);
+	assert_close(sphere, berlin.haversine_distance(&paris), MM);
+
+	let within: (bool, bool, bool) = diesel::select((
+		st_dwithin(berlin, paris, spheroid + 1.0),
+		st_dwithin(berlin, paris, spheroid - 1.0),
+		st_dwithin_with_spheroid(berlin, paris, sphere + 1.0, false),
+	))
+	.get_result(conn)
+	.unwrap();
+	assert_eq!(within, (true, false, true));
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn bearing_and_projection() {
+	let conn = &mut connection();
+	let (berlin, paris) = (pt(13.4, 52.5), pt(2.35, 48.86));
+	let azimuth: Option<f64> = diesel::select(st_azimuth(berlin, paris)).get_result(conn).unwrap();
+	assert_close(azimuth.unwrap(), berlin.initial_bearing(&paris).unwrap(), 1e-9);
+
+	let projected: GeogPoint = diesel::select(st_project(berlin, 100_000.0, 1.0)).get_result(conn).unwrap();
+	let expected = berlin.destination(1.0, 100_000.0);
+	assert_close(projected.vincenty_distance(&expected).unwrap(), 0.0, MM);
+	assert_eq!(projected.srid, Some(4326));
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn measurement() {
+	let conn = &mut connection();
+	let polygon = square(0.0, 0.0, 1.0);
+	let (area, sphere_area, perimeter, sphere_perimeter): (f64, f64, f64, f64) = diesel::select((
+		st_area(polygon.clone()),
+		st_area_with_spheroid(polygon.clone(), false),
+		st_perimeter(polygon.clone()),
+		st_perimeter_with_spheroid(polygon.clone(), false),
+	))
+	.get_result(conn)
+	.unwrap();
+	// A 1° square on the equator covers about 12 300 km².
+	assert_close(area / 1.2308e10, 1.0, 1e-3);
+	assert_close(sphere_area / area, 1.0, 1e-2);
+	let edges: f64 = polygon.rings[0].windows(2).map(|e| e[0].vincenty_distance(&e[1]).unwrap()).sum();
+	assert_close(perimeter, edges, MM);
+	assert_close(sphere_perimeter / perimeter, 1.0, 1e-2);
+
+	let path = line(&[(13.4, 52.5), (2.35, 48.86)]);
+	let (length, sphere_length): (f64, f64) = diesel::select((
+		st_length(path.clone()),
+		st_length_with_spheroid(path.clone(), false),
+	))
+	.get_result(conn)
+	.unwrap();
+	assert_close(length, path.points[0].vincenty_distance(&path.points[1]).unwrap(), MM);
+	assert_close(sphere_length, path.points[0].haversine_distance(&path.points[1]), MM);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn construction() {
+	let conn = &mut connection();
+	let center = pt(13.4, 52.5);
+	let buffer: GeogPolygon = diesel::select(st_buffer(center, 1000.0)).get_result(conn).unwrap();
+	let (covers, area): (bool, f64) = diesel::select((st_covers(buffer.clone(), center), st_area(buffer)))
+		.get_result(conn)
+		.unwrap();
+	assert!(covers);
+	assert_close(area / (std::f64::consts::PI * 1e6), 1.0, 1e-2);
+
+	let centroid: GeogPoint = diesel::select(st_centroid(square(0.0, 0.0, 1.0))).get_result(conn).unwrap();
+	assert_close(centroid.x, 0.5, 1e-3);
+	assert_close(centroid.y, 0.5, 1e-3);
+
+	let path = line(&[(13.4, 52.5), (2.35, 48.86)]);
+	let segmented: GeogLineString = diesel::select(st_segmentize(path.clone(), 10_000.0)).get_result(conn).unwrap();
+	assert!(segmented.points.len() > 80);
+	let (before, after): (f64, f64) = diesel::select((st_length(path), st_length(segmented))).get_result(conn).unwrap();
+	assert_close(before, after, MM);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn predicates() {
+	let conn = &mut connection();
+	let (a, b, far) = (square(0.0, 0.0, 2.0), square(1.0, 1.0, 2.0), square(10.0, 10.0, 1.0));
+	let inner = square(0.5, 0.5, 1.0);
+	let results: (bool, bool, bool, bool, bool) = diesel::select((
+		st_intersects(a.clone(), b.clone()),
+		st_intersects(a.clone(), far.clone()),
+		st_covers(a.clone(), inner.clone()),
+		st_coveredby(inner.clone(), a.clone()),
+		st_coveredby(b.clone(), a.clone()),
+	))
+	.get_result(conn)
+	.unwrap();
+	assert_eq!(results, (true, false, true, true, false));
+
+	let (overlap, whole): (f64, f64) = diesel::select((st_area(st_intersection(a.clone(), b)), st_area(a)))
+		.get_result(conn)
+		.unwrap();
+	assert!(overlap > 0.0 && overlap < whole / 2.0);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn text_formats() {
+	let conn = &mut connection();
+	let (parsed, text, json): (GeogPoint, String, String) = diesel::select((
+		st_geogfromtext("SRID=4326;POINT(1 2)"),
+		st_astext(pt(1.0, 2.0)),
+		st_asgeojson(pt(1.0, 2.0)),
+	))
+	.get_result(conn)
+	.unwrap();
+	assert_eq!(parsed, pt(1.0, 2.0));
+	assert_eq!(text, "POINT(1 2)");
+	assert_eq!(json, r#"{"type":"Point","coordinates":[1,2]}"#);
+
+	// The text output of geography values is hex EWKB.
+	let hex: String = diesel::select(sql::<diesel::sql_types::Text>("'SRID=4326;POINT(1 2)'::geography::text"))
+		.get_result(conn)
+		.unwrap();
+	assert_eq!(GeogPoint::from_hex_ewkb(&hex).unwrap(), pt(1.0, 2.0));
+	assert_eq!(pt(1.0, 2.0).to_hex_ewkb(), hex);
+
+	let polygon = GeogPolygon { srid: None, ..square(0.0, 0.0, 1.0) };
+	let text: String = diesel::select(st_astext(polygon.clone())).get_result(conn).unwrap();
+	assert_eq!(text.parse::<GeogPolygon>().unwrap().to_string(), polygon.to_string());
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn operators() {
+	let conn = &mut connection();
+	let (near, far) = (pt(13.4, 52.5), pt(2.35, 48.86));
+	diesel::insert_into(shapes::table)
+		.values(&vec![shapes::g.eq(far), shapes::g.eq(near)])
+		.execute(conn)
+		.unwrap();
+
+	let nearest: GeogPoint = shapes::table
+		.select(shapes::g)
+		.order(shapes::g.distance_knn(pt(13.0, 52.0)))
+		.first(conn)
+		.unwrap();
+	assert_eq!(nearest, near);
+
+	let overlapping: i64 = shapes::table
+		.filter(shapes::g.bbox_overlaps(square(13.0, 52.0, 1.0)))
+		.count()
+		.get_result(conn)
+		.unwrap();
+	assert_eq!(overlapping, 1);
+
+	let same: i64 = shapes::table.filter(shapes::g.same_as(far)).count().get_result(conn).unwrap();
+	assert_eq!(same, 1);
+
+	// Nullable columns work too.
+	diesel::insert_into(places::table)
+		.values(&vec![places::location.eq(Some(far)), places::location.eq(None)])
+		.execute(conn)
+		.unwrap();
+	let distances: Vec<Option<f64>> = places::table
+		.select(places::location.distance_knn(near))
+		.order(places::id)
+		.load(conn)
+		.unwrap();
+	assert!(distances[0].unwrap() > 800_000.0);
+	assert_eq!(distances[1], None);
+	let overlapping: i64 = places::table
+		.filter(places::location.bbox_overlaps(square(2.0, 48.0, 1.0)).and(places::location.same_as(far)))
+		.count()
+		.get_result(conn)
+		.unwrap();
+	assert_eq!(overlapping, 1);
+	let planar: Option<GeogPoint> =
+		places::table.select(places::location.as_geometry()).order(places::id.desc()).first(conn).unwrap();
+	assert_eq!(planar, None);
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn bounding_boxes() {
+	let conn = &mut connection();
+	let empty: Option<GeogBox> = planar::table.select(st_extent(planar::g)).get_result(conn).unwrap();
+	assert_eq!(empty, None);
+
+	diesel::insert_into(planar::table)
+		.values(&vec![
+			planar::g.eq(GeogPointZ { x: 0.0, y: 1.0, z: 2.0, srid: Some(4326) }),
+			planar::g.eq(GeogPointZ { x: 3.0, y: -1.0, z: 5.0, srid: Some(4326) }),
+		])
+		.execute(conn)
+		.unwrap();
+	let (extent, extent_3d): (Option<GeogBox>, Option<GeogBox3d>) =
+		planar::table.select((st_extent(planar::g), st_3dextent(planar::g))).get_result(conn).unwrap();
+	assert_eq!(extent, Some(GeogBox { xmin: 0.0, ymin: -1.0, xmax: 3.0, ymax: 1.0 }));
+	assert_eq!(extent_3d, Some(GeogBox3d { xmin: 0.0, ymin: -1.0, zmin: 2.0, xmax: 3.0, ymax: 1.0, zmax: 5.0 }));
+	let result = diesel::select(sql::<Box2d>("'BOX(0 0)'")).get_result::<GeogBox>(conn);
+	assert_eq!(geography_error(result), Some(GeographyError::InvalidBox(WktError::UnexpectedToken(")".into()))));
+
+	let envelope: GeogPolygon = diesel::select(st_makeenvelope(0.0, 0.0, 1.0, 2.0, 4326)).get_result(conn).unwrap();
+	assert_eq!(envelope.srid, Some(4326));
+	assert_eq!(envelope.rings.len(), 1);
+	assert_eq!(envelope.rings[0].len(), 5);
+	assert!(envelope.rings[0].iter().all(|p| (p.x == 0.0 || p.x == 1.0) && (p.y == 0.0 || p.y == 2.0)));
+
+	// Bound boxes are sent as text and cast by PostGIS.
+	let viewport = GeogBox { xmin: 0.0, ymin: 0.0, xmax: 1.0, ymax: 2.0 };
+	let polygon: GeogPolygon = diesel::select(box2d_as_geometry(viewport)).get_result(conn).unwrap();
+	assert_eq!(polygon.rings, envelope.rings);
+	let inside: i64 = planar::table
+		.filter(planar::g.as_geography().bbox_overlaps(box2d_as_geometry(viewport).as_geography()))
+		.count()
+		.get_result(conn)
+		.unwrap();
+	assert_eq!(inside, 1);
+	let cube = GeogBox3d { xmin: 0.0, ymin: 0.0, zmin: 0.0, xmax: 1.0, ymax: 1.0, zmax: 1.0 };
+	let extent: Option<GeogBox3d> = diesel::select(st_3dextent(box3d_as_geometry(cube))).get_result(conn).unwrap();
+	assert_eq!(extent, Some(cube));
+}
+
+#[test]
+#[ignore = "needs a PostGIS database in DATABASE_URL"]
+fn casts() {
+	let conn = &mut connection();
+	let query = shapes::table.select(shapes::g.as_geometry().as_geography());
+	let sql = diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string();
+	assert!(sql.contains(r#"CAST(CAST("shapes"."g" AS geometry) AS geography)"#), "{}", sql);
+
+	diesel::insert_into(shapes::table).values(shapes::g.eq(square(0.0, 0.0, 1.0))).execute(conn).unwrap();
+	let planar: GeogPolygon = shapes::table.select(shapes::g.as_geometry()).first(conn).unwrap();
+	assert_eq!(planar.to_string(), square(0.0, 0.0, 1.0).to_string());
+	let round_trip: GeogPolygon = query.first(conn).unwrap();
+	assert_eq!(round_trip.to_string(), square(0.0, 0.0, 1.0).to_string());
+
+	let extent: Option<GeogBox> = shapes::table.select(st_extent(shapes::g.as_geometry())).get_result(conn).unwrap();
+	assert_eq!(extent, Some(GeogBox { xmin: 0.0, ymin: 0.0, xmax: 1.0, ymax: 1.0 }));
+	let nad83: i32 = shapes::table
+		.select(st_srid(st_transform(shapes::g.as_geometry(), 4269).as_geography()))
+		.first(conn)
+		.unwrap();
+	assert_eq!(nad83, 4269);
+}
@@ -0,0 +1,122 @@
+//! The SQL generated for operators and casts, checked without a database.
+
+extern crate diesel;
+extern crate diesel_geography;
+
+use diesel::debug_query;
+use diesel::pg::Pg;
+use diesel::prelude::*;
+use diesel::query_dsl::LoadQuery;
+use diesel_geography::expression_methods::*;
+use diesel_geography::functions::*;
+use diesel_geography::types::*;
+
+table! {
+	use diesel::sql_types::*;
+	use diesel_geography::sql_types::*;
+
+	places (id) {
+		id -> Int4,
+		location -> Geography,
+		nullable_location -> Nullable<Geography>,
+		planar -> Nullable<Geometry>,
+		typed -> TypedGeography<Srid4326>,
+	}
+}
+
+fn here() -> GeogPoint {
+	GeogPoint { x: 13.4, y: 52.5, srid: Some(4326) }
+}
+
+/// Checks at compile time that the query loads rows of type `U`.
+fn loads<U, Q: LoadQuery<'static, PgConnection, U>>(_: &Q) {}
+
+macro_rules! assert_sql {
+	($query:expr, $sql:expr) => {{
+		let sql = debug_query::<Pg, _>(&$query).to_string();
+		assert!(sql.starts_with($sql), "{}", sql);
+	}};
+}
+
+#[test]
+fn operators() {
+	assert_sql!(
+		places::table.select(places::id).filter(places::location.bbox_overlaps(here())),
+		r#"SELECT "places"."id" FROM "places" WHERE "places"."location" && $1"#
+	);
+	assert_sql!(
+		places::table.select(places::id).order(places::location.distance_knn(here())),
+		r#"SELECT "places"."id" FROM "places" ORDER BY "places"."location" <-> $1"#
+	);
+	// PostGIS only defines `~=` for geometry.
+	assert_sql!(
+		places::table.select(places::id).filter(places::location.same_as(here())),
+		r#"SELECT "places"."id" FROM "places" WHERE CAST("places"."location" AS geometry) ~= CAST($1 AS geometry)"#
+	);
+}
+
+#[test]
+fn nullable_operators() {
+	let query = places::table.select(places::nullable_location.distance_knn(here()));
+	assert_sql!(query, r#"SELECT "places"."nullable_location" <-> $1 FROM "places""#);
+	loads::<Option<f64>, _>(&query);
+	assert_sql!(
+		places::table.select(places::id).filter(places::nullable_location.bbox_overlaps(here())),
+		r#"SELECT "places"."id" FROM "places" WHERE "places"."nullable_location" && $1"#
+	);
+}
+
+#[test]
+fn casts() {
+	assert_sql!(
+		places::table.select(places::location.as_geometry().as_geography()),
+		r#"SELECT CAST(CAST("places"."location" AS geometry) AS geography) FROM "places""#
+	);
+	let query = places::table.select((places::nullable_location.as_geometry(), places::planar.as_geography()));
+	assert_sql!(
+		query,
+		r#"SELECT CAST("places"."nullable_location" AS geometry), CAST("places"."planar" AS geography) FROM "places""#
+	);
+	loads::<(Option<GeogPoint>, Option<GeogPoint>), _>(&query);
+}
+
+#[test]
+fn boxes() {
+	assert_sql!(
+		places::table.select(st_extent(places::location.as_geometry())),
+		r#"SELECT CAST(ST_Extent(CAST("places"."location" AS geometry)) AS text) FROM "places""#
+	);
+}
+
+#[test]
+fn bound_boxes() {
+	let viewport = GeogBox { xmin: 13.0, ymin: 52.0, xmax: 14.0, ymax: 53.0 };
+	assert_sql!(
+		places::table.select(places::id).filter(places::location.bbox_overlaps(box2d_as_geometry(viewport).as_geography())),
+		r#"SELECT "places"."id" FROM "places" WHERE "places"."location" && CAST(CAST(CAST($1 AS box2d) AS geometry) AS geography) -- binds: [GeogBox"#
+	);
+	let cube = GeogBox3d { xmin: 0.0, ymin: 0.0, zmin: 0.0, xmax: 1.0, ymax: 1.0, zmax: 1.0 };
+	assert_sql!(
+		diesel::select(box3d_as_geometry(cube)),
+		"SELECT CAST(CAST($1 AS box3d) AS geometry) -- binds: [GeogBox3d"
+	);
+}
+
+#[test]
+fn typed() {
+	let query = places::table.select(st_distance(places::typed.as_geography(), here()));
+	assert_sql!(query, r#"SELECT ST_Distance(CAST("places"."typed" AS geography), $1) FROM "places""#);
+	loads::<f64, _>(&query);
+	assert_sql!(
+		places::table.select(places::id).order(places::typed.as_geography().distance_knn(here())),
+		r#"SELECT "places"."id" FROM "places" ORDER BY CAST("places"."typed" AS geography) <-> $1"#
+	);
+}
+
+#[test]
+fn eq_any() {
+	assert_sql!(
+		places::table.select(places::id).filter(places::location.eq_any(vec![here(), here()])),
+		r#"SELECT "places"."id" FROM "places" WHERE ("places"."location" = ANY($1)) -- binds: [[GeogPoint"#
+	);
+}
@@ -0,0 +1,149 @@
+//! Parsing and printing (E)WKT, without a database.
+
+extern crate diesel_geography;
+
+use diesel_geography::types::*;
+use diesel_geography::wkt::{Dimension, WktError};
+
+fn pt(x: f64, y: f64) -> GeogPoint {
+	GeogPoint { x, y, srid: Some(4326) }
+}
+
+/// Checks that `wkt` parses as `T` and prints back unchanged.
+fn round_trip<T>(wkt: &str) -> T
+where
+	T: std::str::FromStr<Err = WktError> + std::fmt::Display,
+{
+	let value: T = wkt.parse().unwrap_or_else(|e| panic!("{}: {}", wkt, e));
+	assert_eq!(value.to_string(), wkt);
+	value
+}
+
+#[test]
+fn ewkt_round_trips() {
+	assert_eq!(round_trip::<GeogPoint>("SRID=4326;POINT(13.4 52.5)"), pt(13.4, 52.5));
+	assert_eq!(round_trip::<GeogPoint>("POINT(1 2)").srid, None);
+	round_trip::<GeogLineString>("SRID=4326;LINESTRING(0 0,1 1,2 0)");
+	round_trip::<GeogPolygon>("SRID=4326;POLYGON((0 0,4 0,4 4,0 0),(1 1,2 1,1 2,1 1))");
+	round_trip::<GeogMultiPoint>("SRID=4326;MULTIPOINT((1 2),(3 4))");
+	round_trip::<GeogMultiLineString>("SRID=4326;MULTILINESTRING((0 0,1 1),(2 2,3 3))");
+	round_trip::<GeogMultiPolygon>("SRID=4326;MULTIPOLYGON(((0 0,1 0,0 1,0 0)),((5 5,6 5,5 6,5 5)))");
+	round_trip::<GeogGeometryCollection>("SRID=4326;GEOMETRYCOLLECTION(POINT(1 2),LINESTRING(0 0,1 1))");
+	round_trip::<GeogAny>("SRID=4269;LINESTRING EMPTY");
+	round_trip::<GeogPointZ>("SRID=4326;POINT Z (1 2 3)");
+	round_trip::<GeogPointM>("POINT M (1 2 4)");
+	round_trip::<GeogPointZM>("SRID=4326;POINT ZM (1 2 3 4)");
+	round_trip::<GeogBox>("BOX(0 -1,3 1)");
+	round_trip::<GeogBox3d>("BOX3D(0 -1 2,3 1 5)");
+
+	// Members share the SRID of the collection.
+	let line: GeogLineString = "SRID=4326;LINESTRING(0 0,1 1)".parse().unwrap();
+	assert_eq!(line.points, vec![pt(0.0, 0.0), pt(1.0, 1.0)]);
+	// Case and whitespace don't matter.
+	assert_eq!("srid=4326; point ( 13.4  52.5 )".parse::<GeogPoint>(), Ok(pt(13.4, 52.5)));
+}
+
+#[test]
+fn empty_points() {
+	assert_eq!("POINT EMPTY".parse::<GeogPoint>(), Err(WktError::EmptyPoint));
+	assert_eq!("POINT Z EMPTY".parse::<GeogPointZ>(), Err(WktError::EmptyPoint));
+	assert_eq!("SRID=4326;POINT EMPTY".parse::<GeogAny>(), Err(WktError::EmptyPoint));
+
+	// Empty points are NaN coordinates, as in EWKB.
+	let empty = GeogPoint { x: f64::NAN, y: f64::NAN, srid: Some(4326) };
+	assert_eq!(empty.to_string(), "SRID=4326;POINT EMPTY");
+	assert_eq!(GeogPointZM { x: f64::NAN, y: f64::NAN, z: f64::NAN, m: f64::NAN, srid: None }.to_string(), "POINT ZM EMPTY");
+	let multi = GeogMultiPoint { points: vec![empty, pt(1.0, 2.0)], srid: Some(4326) };
+	assert_eq!(multi.to_string(), "SRID=4326;MULTIPOINT(EMPTY,(1 2))");
+	let parsed: GeogMultiPoint = round_trip("SRID=4326;MULTIPOINT(EMPTY,(1 2))");
+	assert!(parsed.points[0].x.is_nan() && parsed.points[0].y.is_nan());
+	assert_eq!(parsed.points[1], pt(1.0, 2.0));
+	let collection: GeogGeometryCollection = round_trip("GEOMETRYCOLLECTION(POINT EMPTY,POINT(1 2))");
+	match collection.geometries[0] {
+		GeogAny::Point(ref p) => assert!(p.x.is_nan() && p.y.is_nan()),
+		ref g => panic!("expected an empty point, found {}", g),
+	}
+
+	// Empty members of other lists are a bare `EMPTY` too, as PostGIS writes them.
+	let multi: GeogMultiLineString = round_trip("SRID=4326;MULTILINESTRING(EMPTY,(0 0,1 1))");
+	assert!(multi.lines[0].points.is_empty());
+	assert_eq!(multi.lines[1].points, vec![pt(0.0, 0.0), pt(1.0, 1.0)]);
+	round_trip::<GeogPolygon>("POLYGON(EMPTY)");
+	round_trip::<GeogMultiPolygon>("MULTIPOLYGON(EMPTY)");
+	round_trip::<GeogMultiPolygon>("MULTIPOLYGON(EMPTY,((0 0,1 0,0 1,0 0),EMPTY))");
+	round_trip::<GeogAny>("GEOMETRYCOLLECTION(LINESTRING EMPTY,MULTIPOLYGON EMPTY)");
+	let polygon = GeogPolygon { rings: vec![vec![]], srid: None };
+	assert_eq!(polygon.to_string(), "POLYGON(EMPTY)");
+	assert_eq!("LINESTRING(EMPTY)".parse::<GeogLineString>(), Err(WktError::UnexpectedToken("EMPTY".into())));
+}
+
+#[test]
+fn dimensions() {
+	let z = GeogPointZ { x: 1.0, y: 2.0, z: 3.0, srid: None };
+	assert_eq!("POINT Z (1 2 3)".parse(), Ok(z));
+	assert_eq!("POINTZ(1 2 3)".parse(), Ok(z));
+	// PostGIS writes Z coordinates without a tag.
+	assert_eq!("POINT(1 2 3)".parse(), Ok(z));
+	let m = GeogPointM { x: 1.0, y: 2.0, m: 4.0, srid: None };
+	assert_eq!("POINT M (1 2 4)".parse(), Ok(m));
+	assert_eq!("POINTM(1 2 4)".parse(), Ok(m));
+	let zm = GeogPointZM { x: 1.0, y: 2.0, z: 3.0, m: 4.0, srid: None };
+	assert_eq!("POINT ZM (1 2 3 4)".parse(), Ok(zm));
+	assert_eq!("POINT(1 2 3 4)".parse(), Ok(zm));
+}
+
+#[test]
+fn dimension_mismatches() {
+	let wrong = |expected, found| WktError::WrongDimension { expected, found };
+	assert_eq!("POINT Z (1 2 3)".parse::<GeogPoint>().unwrap_err(), wrong(Dimension::Xy, Dimension::Xyz));
+	assert_eq!("POINT(1 2)".parse::<GeogPointZ>().unwrap_err(), wrong(Dimension::Xyz, Dimension::Xy));
+	assert_eq!("POINT M (1 2 3)".parse::<GeogPointZ>().unwrap_err(), wrong(Dimension::Xyz, Dimension::Xym));
+	assert_eq!("POINT Z (1 2 3)".parse::<GeogPointZM>().unwrap_err(), wrong(Dimension::Xyzm, Dimension::Xyz));
+	// The tag and the coordinates disagree.
+	assert_eq!("POINT Z (1 2)".parse::<GeogPointZ>().unwrap_err(), wrong(Dimension::Xyz, Dimension::Xy));
+	// All coordinates of a geometry have the same dimension.
+	assert_eq!("LINESTRING(0 0,1 1 1)".parse::<GeogLineString>().unwrap_err(), wrong(Dimension::Xy, Dimension::Xyz));
+	assert_eq!("LINESTRING Z (0 0 0,1 1 1)".parse::<GeogLineString>().unwrap_err(), wrong(Dimension::Xy, Dimension::Xyz));
+}
+
+#[test]
+fn multipoint_forms() {
+	let expected = GeogMultiPoint { points: vec![pt(1.0, 2.0), pt(3.0, 4.0)], srid: Some(4326) };
+	assert_eq!("SRID=4326;MULTIPOINT((1 2),(3 4))".parse(), Ok(expected.clone()));
+	assert_eq!("SRID=4326;MULTIPOINT(1 2,3 4)".parse(), Ok(expected.clone()));
+	assert_eq!("SRID=4326;MULTIPOINT(1 2,(3 4))".parse(), Ok(expected));
+}
+
+#[test]
+fn errors() {
+	assert_eq!(
+		"SRID=4326;LINESTRING(0 0,1 1)".parse::<GeogPoint>(),
+		Err(WktError::WrongType { expected: "POINT", found: "LINESTRING" })
+	);
+	assert_eq!("CIRCULARSTRING(0 0,1 1,2 0)".parse::<GeogAny>(), Err(WktError::UnknownType("CIRCULARSTRING".into())));
+	assert_eq!("POINT(1 2".parse::<GeogPoint>(), Err(WktError::UnexpectedEnd));
+	assert_eq!("POINT(1 x)".parse::<GeogPoint>(), Err(WktError::UnexpectedToken("x".into())));
+	assert_eq!("POINT(1 2.3.4)".parse::<GeogPoint>(), Err(WktError::InvalidNumber("2.3.4".into())));
+	assert_eq!("POINT(1 2) ?".parse::<GeogPoint>(), Err(WktError::UnexpectedToken("?".into())));
+	assert_eq!("BOX(0 0,1 1,2 2)".parse::<GeogBox>(), Err(WktError::UnexpectedToken(",".into())));
+
+	// Trailing tokens.
+	assert_eq!("POINT(1 2) POINT(3 4)".parse::<GeogPoint>(), Err(WktError::UnexpectedToken("POINT".into())));
+	assert_eq!("POINT(1 2))".parse::<GeogPoint>(), Err(WktError::UnexpectedToken(")".into())));
+	assert_eq!("LINESTRING EMPTY (0 0)".parse::<GeogLineString>(), Err(WktError::UnexpectedToken("(".into())));
+
+	// Bad SRIDs.
+	assert_eq!("SRID=abc;POINT(1 2)".parse::<GeogPoint>(), Err(WktError::InvalidSrid("abc".into())));
+	assert_eq!("SRID=4326.5;POINT(1 2)".parse::<GeogPoint>(), Err(WktError::InvalidSrid("4326.5".into())));
+	assert_eq!("SRID=99999999999;POINT(1 2)".parse::<GeogPoint>(), Err(WktError::InvalidSrid("99999999999".into())));
+	assert_eq!("SRID=4326 POINT(1 2)".parse::<GeogPoint>(), Err(WktError::UnexpectedToken("POINT".into())));
+}
+
+#[test]
+fn nesting() {
+	// The same limit as for EWKB.
+	let nested = |depth| format!("{}POINT(1 2){}", "GEOMETRYCOLLECTION(".repeat(depth), ")".repeat(depth));
+	assert!(nested(32).parse::<GeogAny>().is_ok());
+	assert_eq!(nested(33).parse::<GeogAny>(), Err(WktError::TooDeeplyNested));
+	assert_eq!("GEOMETRYCOLLECTION(".repeat(20_000).parse::<GeogAny>(), Err(WktError::TooDeeplyNested));
+}